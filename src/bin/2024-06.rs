@@ -0,0 +1,292 @@
+use std::{collections::HashSet, fmt::Display};
+
+use advent_of_code::{
+    cycle, debug,
+    direction::Direction,
+    grid::Grid,
+    point::Point2,
+    template::visualize::{self, Color, Frame},
+};
+
+advent_of_code::solution!("2024-06", parse);
+
+#[derive(Clone, Debug, PartialEq, Copy)]
+pub enum Tile {
+    Empty,
+    Walked,
+    Walked2(Direction),
+    Guard(Direction),
+    Obstacle,
+    Obstacle2,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+struct Guard {
+    pos: Point2,
+    dir: Direction,
+}
+
+/// The map does not contain a guard.
+#[derive(Debug, PartialEq, Eq)]
+pub struct NoGuardError;
+
+impl Display for NoGuardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "No guard found")
+    }
+}
+
+fn find_guard(grid: &Grid<Tile>) -> Result<Guard, NoGuardError> {
+    grid.iter()
+        .find_map(|(pos, tile)| match tile {
+            Tile::Guard(dir) => Some(Guard { pos, dir: *dir }),
+            _ => None,
+        })
+        .ok_or(NoGuardError)
+}
+
+pub fn part_one(grid: &Grid<Tile>) -> Result<u64, NoGuardError> {
+    let guard = find_guard(grid)?;
+    debug!("Guard: {guard:?}");
+
+    let walked_grid = simulate_walk(grid.clone(), guard);
+    let walked_tile_count = walked_grid.positions(|tile| *tile == Tile::Walked).count();
+
+    debug!("Walked grid:\n{walked_grid}");
+    Ok(walked_tile_count as u64)
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Tile::Empty => write!(f, "."),
+            Tile::Walked => write!(f, "X"),
+            Tile::Guard(dir) | Tile::Walked2(dir) => write!(f, "{dir}"),
+            Tile::Obstacle => write!(f, "#"),
+            Tile::Obstacle2 => write!(f, "O"),
+        }
+    }
+}
+
+pub fn part_two(grid: &Grid<Tile>) -> Result<u64, NoGuardError> {
+    let mut guard = find_guard(grid)?;
+    let mut grid = grid.clone();
+    debug!("Guard: {guard:?}");
+
+    let orig_pos = guard.pos;
+
+    let mut obstacle_set: HashSet<Point2> = HashSet::new();
+    loop {
+        grid[guard.pos] = Tile::Walked2(guard.dir);
+        if let Some(obstacle_pos) = get_obstacle_loop_pos(&grid, &guard) {
+            obstacle_set.insert(obstacle_pos);
+        }
+        match get_next_guard(&guard, &grid) {
+            Some(new_guard) => {
+                grid[new_guard.pos] = Tile::Guard(new_guard.dir);
+                guard = new_guard;
+            }
+
+            None => {
+                debug!("Guard is outside the grid {guard:?}");
+                break;
+            }
+        }
+    }
+    obstacle_set.remove(&orig_pos);
+
+    for pos in obstacle_set.iter() {
+        grid[*pos] = Tile::Obstacle2;
+    }
+    debug!("Walked grid:\n{grid}");
+
+    Ok(obstacle_set.iter().len() as u64)
+}
+
+fn simulate_walk(mut grid: Grid<Tile>, guard: Guard) -> Grid<Tile> {
+    let new_guard_opt = get_next_guard(&guard, &grid);
+    grid[guard.pos] = Tile::Walked;
+    match new_guard_opt {
+        Some(new_guard) => {
+            grid[new_guard.pos] = Tile::Guard(new_guard.dir);
+            if new_guard.dir != guard.dir {
+                visualize::record(|| {
+                    walk_frame(&grid, &new_guard).caption(format!(
+                        "The guard turns {} at {}",
+                        new_guard.dir, new_guard.pos
+                    ))
+                });
+            }
+            simulate_walk(grid, new_guard)
+        }
+        None => {
+            debug!("Guard is outside the grid {guard:?}");
+            visualize::record(|| {
+                walk_frame(&grid, &guard)
+                    .caption(format!("The guard leaves the grid at {}", guard.pos))
+            });
+            grid
+        }
+    }
+}
+
+/// A frame of the guard's walk with the walked tiles and the guard highlighted.
+fn walk_frame(grid: &Grid<Tile>, guard: &Guard) -> Frame {
+    Frame::from_grid(grid, |tile| tile.to_string().chars().next().unwrap_or(' '))
+        .highlight_all(grid.positions(|tile| *tile == Tile::Walked), Color::Yellow)
+        .highlight(guard.pos, Color::Red)
+}
+
+/// Checks whether the guard walks in a loop instead of leaving the grid.
+fn has_loop(grid: &Grid<Tile>, guard: Guard) -> bool {
+    cycle::find_cycle(guard, |guard| get_next_guard(guard, grid)).is_some()
+}
+
+fn get_obstacle_loop_pos(grid: &Grid<Tile>, guard: &Guard) -> Option<Point2> {
+    let new_guard = Guard {
+        pos: guard.pos,
+        dir: guard.dir.turn_right(),
+    };
+    let mut new_grid = grid.clone();
+
+    let obstacle_pos = guard.pos + guard.dir.offset();
+    if can_create_obstacle(grid, obstacle_pos) {
+        new_grid[obstacle_pos] = Tile::Obstacle2;
+        if has_loop(&new_grid, new_guard) {
+            Some(obstacle_pos)
+        } else {
+            None
+        }
+    } else {
+        None
+    }
+}
+
+fn can_create_obstacle(grid: &Grid<Tile>, new_pos: Point2) -> bool {
+    matches!(grid.get(new_pos), Some(Tile::Empty))
+}
+
+fn get_next_guard(guard: &Guard, grid: &Grid<Tile>) -> Option<Guard> {
+    let new_guard_pos = guard.pos + guard.dir.offset();
+    if let Some(tile) = grid.get(new_guard_pos) {
+        match tile {
+            Tile::Empty | Tile::Walked | Tile::Walked2(_) => {
+                let new_guard = Guard {
+                    pos: new_guard_pos,
+                    dir: guard.dir,
+                };
+                Some(new_guard)
+            }
+            Tile::Obstacle | Tile::Obstacle2 => {
+                let new_guard = Guard {
+                    pos: guard.pos,
+                    dir: guard.dir.turn_right(),
+                };
+                Some(new_guard)
+            }
+            tile => {
+                debug!("{grid}");
+                panic!("Invalid tile {:?} on position {:?}", tile, new_guard_pos);
+            }
+        }
+    } else {
+        // Guard is outside the grid
+        None
+    }
+}
+
+pub fn parse(input: &str) -> Grid<Tile> {
+    Grid::parse(input, |c| match c {
+        '.' => Tile::Empty,
+        '#' => Tile::Obstacle,
+        '^' => Tile::Guard(Direction::Up),
+        '>' => Tile::Guard(Direction::Right),
+        'v' => Tile::Guard(Direction::Down),
+        '<' => Tile::Guard(Direction::Left),
+        _ => panic!("Invalid character"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
+        assert_eq!(result, Ok(41));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
+        assert_eq!(result, Ok(6));
+    }
+
+    #[test]
+    fn test_part_two_1() {
+        let input = ".#.#...\n\
+                            #...#..\n\
+                            .#.....\n\
+                            #.....#\n\
+                            .....#.\n\
+                            ...^...\n\
+                            .......\n";
+        let result = part_two(&parse(input));
+        assert_eq!(result, Ok(1));
+    }
+
+    #[test]
+    fn test_part_two_2() {
+        let input = ".......\n\
+                            ...##..\n\
+                            ......#\n\
+                            ...^...\n\
+                            .....#.\n\
+                            .......\n\
+                            .......\n";
+        let result = part_two(&parse(input));
+        assert_eq!(result, Ok(1));
+    }
+
+    #[test]
+    fn test_part_two_3() {
+        let input = ".##....\n\
+                            #....#.\n\
+                            ##..#..\n\
+                            ^...#..\n\
+                            ...#...\n\
+                            .......\n\
+                            .......\n";
+        let result = part_two(&parse(input));
+        assert_eq!(result, Ok(0));
+    }
+
+    #[test]
+    fn test_part_two_4() {
+        let input = ".#..\n\
+                            #..#\n\
+                            ....\n\
+                            ^...\n\
+                            #...\n\
+                            .#..\n";
+        let result = part_two(&parse(input));
+        assert_eq!(result, Ok(1));
+    }
+
+    #[test]
+    fn test_part_two_5() {
+        let input = ".#..\n\
+                            ....\n\
+                            ....\n\
+                            #^..\n\
+                            .#..\n\
+                            ....\n";
+        let result = part_two(&parse(input));
+        assert_eq!(result, Ok(1));
+    }
+}
@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+
+use advent_of_code::{
+    equation::{self, Operator, ADD, CONCAT, MUL},
+    parse,
+};
+
+advent_of_code::solution!("2024-07", parse);
+
+pub fn parse(input: &str) -> HashMap<i64, Vec<i64>> {
+    let equations = parse::records(input).unwrap_or_else(|e| panic!("invalid input: {e}"));
+    equations.into_iter().collect()
+}
+
+pub fn part_one(equations: &HashMap<i64, Vec<i64>>) -> Option<u64> {
+    let sum = solution1(equations);
+    Some(sum)
+}
+
+const PART1_OPERATIONS: [Operator; 2] = [ADD, MUL];
+const PART2_OPERATIONS: [Operator; 3] = [ADD, MUL, CONCAT];
+
+fn solution1(equations: &HashMap<i64, Vec<i64>>) -> u64 {
+    equations.iter().fold(0, |acc, (key, value)| {
+        if equation::is_solvable(*key, value, &PART1_OPERATIONS) {
+            acc + (*key as u64)
+        } else {
+            acc
+        }
+    })
+}
+
+pub fn part_two(equations: &HashMap<i64, Vec<i64>>) -> Option<u64> {
+    let sum = solution2(equations);
+
+    Some(sum)
+}
+
+fn solution2(equations: &HashMap<i64, Vec<i64>>) -> u64 {
+    equations.iter().fold(0, |acc, (key, value)| {
+        if equation::is_solvable(*key, value, &PART2_OPERATIONS) {
+            acc + (*key as u64)
+        } else {
+            acc
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
+        assert_eq!(result, Some(3749));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
+        assert_eq!(result, Some(11387));
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::{HashMap, HashSet};
+
+use advent_of_code::{
+    debug,
+    grid::Grid,
+    point::{Point2, Vec2},
+    template::visualize::{self, Color, Frame},
+};
+use itertools::Itertools;
+
+advent_of_code::solution!("2024-08", parse);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tile {
+    Empty,
+    Antenna(char),
+}
+
+pub struct AntennaMap {
+    grid: Grid<Tile>,
+    tile_map: HashMap<Tile, Vec<Point2>>,
+}
+
+pub fn parse(input: &str) -> AntennaMap {
+    let grid = Grid::parse(input, |c| match c {
+        '.' => Tile::Empty,
+        c => Tile::Antenna(c),
+    });
+    let tile_map = get_tile_map(&grid);
+    AntennaMap { grid, tile_map }
+}
+
+fn get_tile_map(grid: &Grid<Tile>) -> HashMap<Tile, Vec<Point2>> {
+    grid.iter().filter(|(_, tile)| **tile != Tile::Empty).fold(
+        HashMap::new(),
+        |mut acc, (pos, tile)| {
+            acc.entry(*tile).or_insert(vec![]).push(pos);
+            acc
+        },
+    )
+}
+
+pub fn part_one(antenna_map: &AntennaMap) -> Option<u64> {
+    let AntennaMap { grid, tile_map } = antenna_map;
+
+    let result = tile_map
+        .values()
+        .map(|antenna_coords| get_all_antenna_antinodes(antenna_coords))
+        .fold(HashSet::new(), |acc, antinodes| {
+            acc.union(&antinodes).cloned().collect()
+        })
+        .iter()
+        .filter(|pos| grid.contains(**pos))
+        .count();
+
+    Some(result as u64)
+}
+
+fn get_all_antenna_antinodes(antennas: &[Point2]) -> HashSet<Point2> {
+    antennas
+        .iter()
+        .combinations(2)
+        .flat_map(|antenna_pairs| {
+            let a = antenna_pairs[0];
+            let b = antenna_pairs[1];
+
+            get_antenna_pair_antinodes(a, b)
+        })
+        .collect()
+}
+
+fn get_antenna_pair_antinodes(a: &Point2, b: &Point2) -> HashSet<Point2> {
+    let dist = get_antenna_dist(a, b);
+    HashSet::from([*a + dist, *b - dist])
+}
+
+fn get_antenna_vec(a: &Point2, b: &Point2) -> Vec2 {
+    get_antenna_dist(a, b).reduced()
+}
+
+fn get_all_antenna_antinodes_two(antennas: &[Point2], grid: &Grid<Tile>) -> HashSet<Point2> {
+    antennas
+        .iter()
+        .combinations(2)
+        .flat_map(|antenna_pairs| {
+            let a = antenna_pairs[0];
+            let b = antenna_pairs[1];
+
+            get_antenna_pair_antinodes_two(a, b, grid)
+        })
+        .collect()
+}
+
+fn get_antenna_pair_antinodes_two(a: &Point2, b: &Point2, grid: &Grid<Tile>) -> HashSet<Point2> {
+    let step = get_antenna_vec(a, b);
+    let a_antinodes = grid
+        .ray(*a, step)
+        .map(|(pos, _)| pos)
+        .collect::<HashSet<Point2>>();
+    let b_antinodes = grid
+        .ray(*b, -step)
+        .map(|(pos, _)| pos)
+        .collect::<HashSet<Point2>>();
+    debug!(
+        "antinodes: {:?}",
+        a_antinodes.union(&b_antinodes).cloned().collect::<Vec<_>>()
+    );
+    a_antinodes.union(&b_antinodes).cloned().collect()
+}
+
+fn get_antenna_dist(a: &Point2, b: &Point2) -> Vec2 {
+    *a - *b
+}
+
+pub fn part_two(antenna_map: &AntennaMap) -> Option<u64> {
+    let AntennaMap { grid, tile_map } = antenna_map;
+
+    let result = tile_map
+        .iter()
+        .map(|(tile, antenna_coords)| {
+            let antinodes = get_all_antenna_antinodes_two(antenna_coords, grid);
+            visualize::record(|| {
+                antinode_frame(grid, &antinodes)
+                    .highlight_all(antenna_coords.iter().copied(), Color::Red)
+                    .caption(format!("Antinodes of the antennas {tile:?}"))
+            });
+            antinodes
+        })
+        .fold(HashSet::new(), |acc, antinodes| {
+            acc.union(&antinodes).cloned().collect()
+        });
+
+    debug!(
+        "{}",
+        grid.render(|pos, _| if result.contains(&pos) { '#' } else { '.' })
+    );
+    visualize::record(|| {
+        antinode_frame(grid, &result).caption(format!("{} antinodes in total", result.len()))
+    });
+    Some(result.len() as u64)
+}
+
+/// A frame of the antenna map with the antinodes highlighted.
+fn antinode_frame(grid: &Grid<Tile>, antinodes: &HashSet<Point2>) -> Frame {
+    Frame::from_grid(grid, |tile| match tile {
+        Tile::Empty => '.',
+        Tile::Antenna(c) => *c,
+    })
+    .highlight_all(antinodes.iter().copied(), Color::Yellow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
+        assert_eq!(result, Some(14));
+    }
+
+    #[test]
+    fn test_two_part_one() {
+        let input = "..........\n\
+                            ..........\n\
+                            ..........\n\
+                            ....a.....\n\
+                            ........a.\n\
+                            .....a....\n\
+                            ..........\n\
+                            ......A...\n\
+                            ..........\n\
+                            ..........\n";
+        let result = part_one(&parse(input));
+        assert_eq!(result, Some(4));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
+        assert_eq!(result, Some(34));
+    }
+}
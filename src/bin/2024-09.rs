@@ -0,0 +1,189 @@
+use std::ops::Range;
+
+use advent_of_code::range_set::RangeSet;
+use itertools::Itertools;
+
+advent_of_code::solution!("2024-09", parse);
+
+#[derive(Clone, Debug, PartialEq, Eq, Copy)]
+pub enum Byte {
+    Empty,
+    File(usize),
+}
+
+pub fn parse(input: &str) -> Vec<Byte> {
+    let mut idx = 0;
+    input
+        .chars()
+        .enumerate()
+        .flat_map(|(i, c)| {
+            let num = c.to_digit(10).unwrap();
+            if i % 2 == 0 {
+                let bytes = parse_file(idx, num);
+                idx += 1;
+                bytes
+            } else {
+                parse_empty_space(num)
+            }
+        })
+        .collect()
+}
+
+fn parse_file(idx: usize, num: u32) -> Vec<Byte> {
+    vec![Byte::File(idx); num as usize]
+}
+
+fn parse_empty_space(num: u32) -> Vec<Byte> {
+    vec![Byte::Empty; num as usize]
+}
+
+pub fn part_one(bytes: &[Byte]) -> Option<u64> {
+    let mut bytes = bytes.to_vec();
+    move_bytes(&mut bytes);
+    let result: usize = bytes
+        .iter()
+        .filter(|byte| **byte != Byte::Empty)
+        .enumerate()
+        .map(|(i, byte)| match byte {
+            Byte::File(idx) => i * idx,
+            Byte::Empty => 0,
+        })
+        .sum();
+    Some(result as u64)
+}
+
+fn move_bytes(bytes: &mut [Byte]) {
+    let mut i = 0;
+    let mut j = bytes.len() - 1;
+    while i <= j {
+        let i_byte = &bytes[i];
+        match i_byte {
+            Byte::File(_) => {
+                i += 1;
+            }
+            Byte::Empty => {
+                j = get_next_j(bytes, j);
+                if j <= i {
+                    break;
+                }
+                let j_byte = bytes[j];
+                bytes[j] = Byte::Empty;
+                bytes[i] = j_byte;
+                i += 1;
+            }
+        }
+    }
+}
+
+fn get_next_j(bytes: &[Byte], mut j: usize) -> usize {
+    let mut j_byte = bytes[j];
+    while j_byte == Byte::Empty {
+        j -= 1;
+        j_byte = bytes[j];
+    }
+    j
+}
+
+pub fn part_two(bytes: &[Byte]) -> Option<u64> {
+    let (files, free_space) = get_files_and_free_space(bytes);
+    let result: usize = get_defragmented_files(files, free_space)
+        .iter()
+        .map(|file| file.blocks.clone().sum::<i64>() as usize * file.id)
+        .sum();
+    Some(result as u64)
+}
+
+#[derive(Clone, Debug)]
+struct File {
+    id: usize,
+    blocks: Range<i64>,
+}
+
+fn get_files_and_free_space(bytes: &[Byte]) -> (Vec<File>, RangeSet) {
+    let mut files = vec![];
+    let mut free_space = RangeSet::new();
+    let mut start = 0;
+    for (byte, chunk) in &bytes.iter().chunk_by(|byte| **byte) {
+        let end = start + chunk.count() as i64;
+        match byte {
+            Byte::File(id) => files.push(File {
+                id,
+                blocks: start..end,
+            }),
+            Byte::Empty => free_space.insert(start..end),
+        }
+        start = end;
+    }
+    (files, free_space)
+}
+
+/// Moves every file once, starting with the highest id, to the leftmost free space that fits it.
+fn get_defragmented_files(mut files: Vec<File>, mut free_space: RangeSet) -> Vec<File> {
+    for file in files.iter_mut().rev() {
+        let len = file.blocks.end - file.blocks.start;
+        match free_space.first_fit(len as u64) {
+            Some(space) if space.start < file.blocks.start => {
+                free_space.remove(space.start..space.start + len);
+                free_space.insert(file.blocks.clone());
+                file.blocks = space.start..space.start + len;
+            }
+            _ => {}
+        }
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
+        assert_eq!(result, Some(1928));
+    }
+    #[test]
+    fn test_two_part_one() {
+        let result = part_one(&parse("1111111111"));
+        assert_eq!(result, Some(23));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
+        assert_eq!(result, Some(2858));
+    }
+    #[test]
+    fn test_two_part_two() {
+        // parse to "0..111.22.333"
+        let result = part_two(&parse("1231213"));
+        assert_eq!(result, Some(117));
+    }
+
+    #[test]
+    fn test_parse() {
+        let result = parse("12345");
+        let expected = vec![
+            Byte::File(0),
+            Byte::Empty,
+            Byte::Empty,
+            Byte::File(1),
+            Byte::File(1),
+            Byte::File(1),
+            Byte::Empty,
+            Byte::Empty,
+            Byte::Empty,
+            Byte::Empty,
+            Byte::File(2),
+            Byte::File(2),
+            Byte::File(2),
+            Byte::File(2),
+            Byte::File(2),
+        ];
+        assert_eq!(result, expected);
+    }
+}
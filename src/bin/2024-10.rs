@@ -0,0 +1,137 @@
+use std::collections::HashSet;
+
+use advent_of_code::{
+    grid::Grid,
+    parse,
+    point::Point2,
+    search,
+    template::visualize::{self, Color, Frame},
+};
+
+advent_of_code::solution!("2024-10", parse);
+
+#[derive(Clone, Debug, PartialEq, Eq, Copy)]
+pub struct Tile {
+    height: u32,
+}
+
+pub fn parse(input: &str) -> Grid<Tile> {
+    parse::digit_grid(input)
+        .unwrap_or_else(|e| panic!("invalid input: {e}"))
+        .map(|height| Tile {
+            height: *height as u32,
+        })
+}
+
+pub fn part_one(tile_map: &Grid<Tile>) -> Option<u64> {
+    let result = tile_map
+        .iter()
+        .map(|(pos, tile)| {
+            if tile.height == 0 {
+                get_trail_head_score(tile_map, pos)
+            } else {
+                0
+            }
+        })
+        .sum::<u64>();
+
+    Some(result)
+}
+
+fn get_trail_head_score(tile_map: &Grid<Tile>, pos: Point2) -> u64 {
+    let trails = search::reachable(pos, |pos| get_uphill_neighbours(tile_map, *pos));
+    let peaks: HashSet<Point2> = trails
+        .iter()
+        .copied()
+        .filter(|pos| tile_map[*pos].height == 9)
+        .collect();
+
+    visualize::record(|| {
+        Frame::from_grid(tile_map, |tile| {
+            char::from_digit(tile.height, 10).unwrap_or('?')
+        })
+        .highlight_all(trails.iter().copied(), Color::Green)
+        .highlight_all(peaks.iter().copied(), Color::Yellow)
+        .highlight(pos, Color::Red)
+        .caption(format!("Trailhead {pos} reaches {} peaks", peaks.len()))
+    });
+
+    peaks.len() as u64
+}
+
+/// Neighbours that are exactly one step higher.
+fn get_uphill_neighbours(tile_map: &Grid<Tile>, pos: Point2) -> impl Iterator<Item = Point2> + '_ {
+    let height = tile_map[pos].height;
+    tile_map
+        .neighbours4(pos)
+        .filter(move |new_pos| tile_map[*new_pos].height == height + 1)
+}
+
+pub fn part_two(tile_map: &Grid<Tile>) -> Option<u64> {
+    let result = tile_map
+        .iter()
+        .map(|(pos, tile)| {
+            if tile.height == 0 {
+                get_num_paths(tile_map, pos)
+            } else {
+                0
+            }
+        })
+        .sum::<u64>();
+
+    Some(result)
+}
+
+fn get_num_paths(tile_map: &Grid<Tile>, pos: Point2) -> u64 {
+    search::count_paths(
+        pos,
+        |pos| get_uphill_neighbours(tile_map, *pos),
+        |pos| tile_map[*pos].height == 9,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_part_one() {
+        let input = "0123\n\
+                           1234\n\
+                           8765\n\
+                           9876";
+
+        let result = part_one(&parse(input));
+        assert_eq!(result, Some(1));
+    }
+
+    #[test]
+    fn test_three_part_one() {
+        let input = "1091911\n\
+                           2991811\n\
+                           3111711\n\
+                           4567654\n\
+                           1118113\n\
+                           1119392\n\
+                           1111201";
+
+        let result = part_one(&parse(input));
+        assert_eq!(result, Some(3));
+    }
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
+        assert_eq!(result, Some(36));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
+        assert_eq!(result, Some(81));
+    }
+}
@@ -0,0 +1,210 @@
+/// Cycle detection for systems whose next state only depends on the current one.
+///
+/// ```
+/// # use advent_of_code::cycle::{self, Cycle};
+/// // 1, 2, 4, 8, 16 % 12 = 4, ...
+/// let step = |n: &u64| n * 2 % 12;
+/// assert_eq!(cycle::brent(1, step), Cycle { start: 2, length: 2 });
+/// assert_eq!(cycle::nth(1, step, 1_000_000_000), 4);
+/// ```
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    hash::Hash,
+};
+
+/// The shape of a sequence of states that eventually repeats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The index of the first state that is part of the cycle.
+    pub start: usize,
+    /// The number of states in the cycle.
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Maps the index of a state to the index of its first occurrence.
+    pub fn index(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Finds the cycle with Floyd's tortoise and hare algorithm without storing any states.
+///
+/// `step` is called roughly three times per state up to the end of the first repetition.
+pub fn floyd<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Finds the cycle with Brent's algorithm without storing any states.
+///
+/// Usually needs fewer calls to `step` than [`floyd`].
+pub fn brent<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Simulates the system until a state repeats.
+///
+/// `step` returns [`None`] when the system halts, in which case there is no cycle.
+pub fn find_cycle<S: Clone + Eq + Hash>(
+    initial: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+) -> Option<Cycle> {
+    let mut seen = HashMap::new();
+    let mut state = initial;
+    let mut index = 0;
+    loop {
+        match seen.entry(state.clone()) {
+            Entry::Occupied(entry) => {
+                let start = *entry.get();
+                return Some(Cycle {
+                    start,
+                    length: index - start,
+                });
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(index);
+            }
+        }
+        state = step(&state)?;
+        index += 1;
+    }
+}
+
+/// Returns the state after `n` steps, skipping over full repetitions of a cycle.
+pub fn nth<S: Clone + Eq + Hash>(initial: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut state = initial;
+
+    for index in 0..n {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle {
+                start,
+                length: index - start,
+            };
+            return states.swap_remove(cycle.index(n));
+        }
+        seen.insert(state.clone(), index);
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+
+    state
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{brent, find_cycle, floyd, nth, Cycle};
+
+    /// 0, 1, 2, 3, 4, 5, 3, 4, 5, ...
+    fn step(n: &u32) -> u32 {
+        if *n == 5 {
+            3
+        } else {
+            n + 1
+        }
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let expected = Cycle {
+            start: 3,
+            length: 3,
+        };
+        assert_eq!(floyd(0, step), expected);
+        assert_eq!(brent(0, step), expected);
+        assert_eq!(find_cycle(0, |n| Some(step(n))), Some(expected));
+
+        let fixed_point = Cycle {
+            start: 0,
+            length: 1,
+        };
+        assert_eq!(floyd(7, |n| *n), fixed_point);
+        assert_eq!(brent(7, |n| *n), fixed_point);
+    }
+
+    #[test]
+    fn returns_none_for_halting_systems() {
+        assert_eq!(find_cycle(0, |n| (*n < 10).then_some(n + 1)), None);
+    }
+
+    #[test]
+    fn fast_forwards_states() {
+        assert_eq!(nth(0, step, 0), 0);
+        assert_eq!(nth(0, step, 4), 4);
+        assert_eq!(nth(0, step, 6), 3);
+        assert_eq!(nth(0, step, 1_000_000_001), 5);
+        for n in 0..20 {
+            let naive = (0..n).fold(0, |state, _| step(&state));
+            assert_eq!(nth(0, step, n), naive);
+        }
+    }
+
+    #[test]
+    fn maps_indices_into_cycle() {
+        let cycle = Cycle {
+            start: 3,
+            length: 3,
+        };
+        assert_eq!(cycle.index(2), 2);
+        assert_eq!(cycle.index(6), 3);
+        assert_eq!(cycle.index(10), 4);
+    }
+}
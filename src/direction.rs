@@ -0,0 +1,200 @@
+/// Compass directions on a grid where `y` grows downwards.
+use std::fmt::Display;
+
+use crate::point::Vec2;
+
+/// One of the four orthogonal directions.
+///
+/// ```
+/// # use advent_of_code::{direction::Direction, point::Vec2};
+/// assert_eq!(Direction::Up.turn_right(), Direction::Right);
+/// assert_eq!(Direction::Up.offset(), Vec2::new(0, -1));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions, clockwise starting at the top.
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// The direction after a quarter turn clockwise.
+    pub fn turn_right(self) -> Self {
+        match self {
+            Self::Up => Self::Right,
+            Self::Right => Self::Down,
+            Self::Down => Self::Left,
+            Self::Left => Self::Up,
+        }
+    }
+
+    /// The direction after a quarter turn counter-clockwise.
+    pub fn turn_left(self) -> Self {
+        self.turn_right().reverse()
+    }
+
+    /// The opposite direction.
+    pub fn reverse(self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    /// The offset of a single step in this direction.
+    pub fn offset(self) -> Vec2 {
+        match self {
+            Self::Up => Vec2::new(0, -1),
+            Self::Right => Vec2::new(1, 0),
+            Self::Down => Vec2::new(0, 1),
+            Self::Left => Vec2::new(-1, 0),
+        }
+    }
+
+    /// The direction of a single step, if the offset is one.
+    pub fn from_offset(offset: Vec2) -> Option<Self> {
+        Self::ALL.into_iter().find(|dir| dir.offset() == offset)
+    }
+}
+
+impl From<Direction> for Vec2 {
+    fn from(dir: Direction) -> Self {
+        dir.offset()
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let arrow = match self {
+            Self::Up => '^',
+            Self::Right => '>',
+            Self::Down => 'v',
+            Self::Left => '<',
+        };
+        write!(f, "{arrow}")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// One of the four orthogonal or four diagonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// All directions, clockwise starting at the top.
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    fn rotate(self, eighths: usize) -> Self {
+        Self::ALL[(self as usize + eighths) % Self::ALL.len()]
+    }
+
+    /// The direction after an eighth turn clockwise.
+    pub fn turn_right(self) -> Self {
+        self.rotate(1)
+    }
+
+    /// The direction after an eighth turn counter-clockwise.
+    pub fn turn_left(self) -> Self {
+        self.rotate(7)
+    }
+
+    /// The opposite direction.
+    pub fn reverse(self) -> Self {
+        self.rotate(4)
+    }
+
+    /// Checks whether this is one of the four diagonal directions.
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// The offset of a single step in this direction.
+    pub fn offset(self) -> Vec2 {
+        match self {
+            Self::Up => Vec2::new(0, -1),
+            Self::UpRight => Vec2::new(1, -1),
+            Self::Right => Vec2::new(1, 0),
+            Self::DownRight => Vec2::new(1, 1),
+            Self::Down => Vec2::new(0, 1),
+            Self::DownLeft => Vec2::new(-1, 1),
+            Self::Left => Vec2::new(-1, 0),
+            Self::UpLeft => Vec2::new(-1, -1),
+        }
+    }
+
+    /// The direction of a single step, if the offset is one.
+    pub fn from_offset(offset: Vec2) -> Option<Self> {
+        Self::ALL.into_iter().find(|dir| dir.offset() == offset)
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(dir: Direction) -> Self {
+        match dir {
+            Direction::Up => Self::Up,
+            Direction::Right => Self::Right,
+            Direction::Down => Self::Down,
+            Direction::Left => Self::Left,
+        }
+    }
+}
+
+impl From<Direction8> for Vec2 {
+    fn from(dir: Direction8) -> Self {
+        dir.offset()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Direction, Direction8};
+    use crate::point::Vec2;
+
+    #[test]
+    fn turns_directions() {
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Right.reverse(), Direction::Left);
+
+        assert_eq!(Direction8::UpLeft.turn_right(), Direction8::Up);
+        assert_eq!(Direction8::Up.turn_left(), Direction8::UpLeft);
+        assert_eq!(Direction8::DownRight.reverse(), Direction8::UpLeft);
+    }
+
+    #[test]
+    fn converts_offsets() {
+        for dir in Direction::ALL {
+            assert_eq!(Direction::from_offset(dir.offset()), Some(dir));
+            assert_eq!(-dir.offset(), dir.reverse().offset());
+            assert_eq!(Direction8::from(dir).offset(), dir.offset());
+        }
+        for dir in Direction8::ALL {
+            assert_eq!(Direction8::from_offset(dir.offset()), Some(dir));
+            assert_eq!(dir.is_diagonal(), dir.offset().manhattan() == 2);
+        }
+        assert_eq!(Direction::from_offset(Vec2::new(1, 1)), None);
+        assert_eq!(Direction8::from_offset(Vec2::new(2, 0)), None);
+    }
+}
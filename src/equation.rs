@@ -0,0 +1,237 @@
+/// Search for operators that turn a list of operands into a target value.
+///
+/// Operators are evaluated strictly from left to right, without precedence. The search runs from the last operand
+/// to the first, undoing one operator at a time, and prunes every branch where an operator cannot be undone.
+///
+/// ```
+/// # use advent_of_code::equation::{self, ADD, MUL};
+/// let expression = equation::solve(3267, &[81, 40, 27], &[ADD, MUL]).unwrap();
+/// assert_eq!(expression.to_string(), "81 + 40 * 27");
+/// assert!(!equation::is_solvable(83, &[17, 5], &[ADD, MUL]));
+/// ```
+use std::fmt::Display;
+
+/// An operator that combines the value so far with the next operand.
+#[derive(Debug, Clone, Copy)]
+pub struct Operator {
+    pub symbol: &'static str,
+    /// Combines the value so far with the next operand, [`None`] if the result is not defined.
+    pub apply: fn(i64, i64) -> Option<i64>,
+    /// Given a result and the operand that produced it, returns the value before, [`None`] if there is none.
+    pub inverse: fn(i64, i64) -> Option<i64>,
+}
+
+impl PartialEq for Operator {
+    fn eq(&self, other: &Self) -> bool {
+        self.symbol == other.symbol
+    }
+}
+
+impl Eq for Operator {}
+
+/// Addition of non-negative operands.
+pub const ADD: Operator = Operator {
+    symbol: "+",
+    apply: i64::checked_add,
+    inverse: |result, rhs| result.checked_sub(rhs).filter(|lhs| *lhs >= 0),
+};
+
+/// Multiplication of non-negative operands.
+///
+/// A multiplication by zero cannot be undone, as every value before it results in zero. [`solve`] handles it
+/// separately and accepts any value that the operands before it evaluate to.
+pub const MUL: Operator = Operator {
+    symbol: "*",
+    apply: i64::checked_mul,
+    inverse: |result, rhs| (rhs != 0 && result % rhs == 0).then(|| result / rhs),
+};
+
+/// Concatenation of the decimal digits of non-negative operands, e.g. `12 || 345 = 12345`.
+pub const CONCAT: Operator = Operator {
+    symbol: "||",
+    apply: |lhs, rhs| lhs.checked_mul(magnitude(rhs))?.checked_add(rhs),
+    inverse: |result, rhs| {
+        let magnitude = magnitude(rhs);
+        (rhs >= 0 && result >= 0 && result % magnitude == rhs).then(|| result / magnitude)
+    },
+};
+
+/// The power of ten that shifts a number to the left of `n`.
+fn magnitude(n: i64) -> i64 {
+    10_i64.pow(n.checked_ilog10().unwrap_or(0) + 1)
+}
+
+/// Operands with the operators that are placed between them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expression {
+    pub operands: Vec<i64>,
+    pub operators: Vec<Operator>,
+}
+
+impl Expression {
+    /// Evaluates the expression from left to right, [`None`] if an operator is not defined for its operands.
+    pub fn evaluate(&self) -> Option<i64> {
+        let (first, rest) = self.operands.split_first()?;
+        rest.iter()
+            .zip(&self.operators)
+            .try_fold(*first, |lhs, (rhs, op)| (op.apply)(lhs, *rhs))
+    }
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, operand) in self.operands.iter().enumerate() {
+            if i > 0 {
+                write!(f, " {} ", self.operators[i - 1].symbol)?;
+            }
+            write!(f, "{operand}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Finds operators that turn `operands` into `target`, returning the first expression found.
+pub fn solve(target: i64, operands: &[i64], operators: &[Operator]) -> Option<Expression> {
+    let mut chosen = Vec::with_capacity(operands.len().saturating_sub(1));
+    if operands.is_empty() || !search(target, operands, operators, &mut chosen) {
+        return None;
+    }
+    chosen.reverse();
+    Some(Expression {
+        operands: operands.to_vec(),
+        operators: chosen,
+    })
+}
+
+/// Checks whether operators exist that turn `operands` into `target`.
+pub fn is_solvable(target: i64, operands: &[i64], operators: &[Operator]) -> bool {
+    solve(target, operands, operators).is_some()
+}
+
+/// Undoes the last operand with every operator. Pushes the operators of a solution in reverse order.
+fn search(
+    target: i64,
+    operands: &[i64],
+    operators: &[Operator],
+    chosen: &mut Vec<Operator>,
+) -> bool {
+    let Some((last, rest)) = operands.split_last() else {
+        return false;
+    };
+    if rest.is_empty() {
+        return target == *last;
+    }
+    for op in operators {
+        // NOTE: the inverse of a multiplication by zero is not a single value, any value before it works.
+        if *op == MUL && *last == 0 && target == 0 {
+            let mut before = Vec::with_capacity(rest.len() - 1);
+            if evaluate_any(rest[0], &rest[1..], operators, &mut before) {
+                chosen.push(*op);
+                chosen.extend(before.into_iter().rev());
+                return true;
+            }
+            continue;
+        }
+        if let Some(lhs) = (op.inverse)(target, *last) {
+            chosen.push(*op);
+            if search(lhs, rest, operators, chosen) {
+                return true;
+            }
+            chosen.pop();
+        }
+    }
+    false
+}
+
+/// Finds operators for which `operands` evaluate to any value, starting from `value`.
+/// Pushes the operators in order.
+fn evaluate_any(
+    value: i64,
+    operands: &[i64],
+    operators: &[Operator],
+    chosen: &mut Vec<Operator>,
+) -> bool {
+    let Some((next, rest)) = operands.split_first() else {
+        return true;
+    };
+    for op in operators {
+        if let Some(value) = (op.apply)(value, *next) {
+            chosen.push(*op);
+            if evaluate_any(value, rest, operators, chosen) {
+                return true;
+            }
+            chosen.pop();
+        }
+    }
+    false
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{is_solvable, solve, Operator, ADD, CONCAT, MUL};
+
+    #[test]
+    fn finds_expressions() {
+        let expression = solve(190, &[10, 19], &[ADD, MUL]).unwrap();
+        assert_eq!(expression.to_string(), "10 * 19");
+        assert_eq!(expression.evaluate(), Some(190));
+
+        let expression = solve(7290, &[6, 8, 6, 15], &[ADD, MUL, CONCAT]).unwrap();
+        assert_eq!(expression.evaluate(), Some(7290));
+        assert_eq!(expression.to_string(), "6 * 8 || 6 * 15");
+
+        assert_eq!(solve(5, &[5], &[ADD]).unwrap().operators, vec![]);
+        assert_eq!(solve(5, &[], &[ADD]), None);
+    }
+
+    #[test]
+    fn rejects_unsolvable_equations() {
+        assert!(!is_solvable(7290, &[6, 8, 6, 15], &[ADD, MUL]));
+        assert!(!is_solvable(161011, &[16, 10, 13], &[ADD, MUL, CONCAT]));
+        assert!(!is_solvable(21037, &[9, 7, 18, 13], &[ADD, MUL, CONCAT]));
+    }
+
+    #[test]
+    fn finds_multiplications_by_zero() {
+        assert!(is_solvable(0, &[5, 0], &[ADD, MUL]));
+        assert!(!is_solvable(5, &[5, 0], &[MUL]));
+
+        let expression = solve(0, &[3, 4, 0, 2], &[ADD, MUL]).unwrap();
+        assert_eq!(expression.to_string(), "3 + 4 * 0 * 2");
+        assert_eq!(expression.evaluate(), Some(0));
+
+        let expression = solve(0, &[i64::MAX / 2 + 1, 2, 0], &[MUL, ADD]).unwrap();
+        assert_eq!(expression.evaluate(), Some(0));
+        assert!(!is_solvable(0, &[i64::MAX / 2 + 1, 2, 0], &[MUL]));
+    }
+
+    #[test]
+    fn inverts_operators() {
+        for op in [ADD, MUL, CONCAT] {
+            for (lhs, rhs) in [(0, 1), (12, 345), (7, 10), (100, 0)] {
+                let Some(result) = (op.apply)(lhs, rhs) else {
+                    continue;
+                };
+                if op == MUL && rhs == 0 {
+                    continue;
+                }
+                assert_eq!((op.inverse)(result, rhs), Some(lhs), "{}", op.symbol);
+            }
+        }
+        assert_eq!((CONCAT.apply)(12, 0), Some(120));
+        assert_eq!((CONCAT.inverse)(1234, 5), None);
+    }
+
+    #[test]
+    fn accepts_custom_operators() {
+        let sub = Operator {
+            symbol: "-",
+            apply: i64::checked_sub,
+            inverse: i64::checked_add,
+        };
+        let expression = solve(-4, &[1, 2, 3], &[ADD, sub]).unwrap();
+        assert_eq!(expression.to_string(), "1 - 2 - 3");
+    }
+}
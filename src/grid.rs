@@ -0,0 +1,401 @@
+/// A rectangular grid of cells, as found in many puzzle inputs.
+use std::{
+    fmt::Display,
+    iter,
+    ops::{Index, IndexMut},
+};
+
+use crate::{
+    direction::{Direction, Direction8},
+    point::{Point2, Vec2},
+};
+
+/// A grid of `width` x `height` cells, stored row by row.
+///
+/// Cells are addressed by a [`Point2`], where `(0, 0)` is the top left corner.
+///
+/// ```
+/// # use advent_of_code::{grid::Grid, point::Point2};
+/// let grid = Grid::parse("#.\n.#", |c| c == '#');
+/// assert_eq!(grid.get(Point2::new(1, 1)), Some(&true));
+/// assert_eq!(grid.get(Point2::new(2, 1)), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from cells in row order.
+    ///
+    /// # Panics
+    /// Panics if the number of cells does not match the dimensions.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "expected {} cells for a {width}x{height} grid",
+            width * height
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Creates a grid where every cell has the same value.
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    /// Parses a grid with one row per line, mapping every character to a cell with `f`.
+    ///
+    /// # Panics
+    /// Panics if the lines are not all of the same length.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Self {
+        let mut width = None;
+        let mut cells = vec![];
+
+        for (y, line) in input.lines().enumerate() {
+            let len = cells.len();
+            cells.extend(line.chars().map(&mut f));
+            let line_width = cells.len() - len;
+
+            match width {
+                None => width = Some(line_width),
+                Some(width) => assert_eq!(
+                    width, line_width,
+                    "line {y} has a length of {line_width}, expected {width}"
+                ),
+            }
+        }
+
+        let width = width.unwrap_or(0);
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        Self::new(width, height, cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Checks whether a point lies within the grid.
+    pub fn contains(&self, point: Point2) -> bool {
+        (0..self.width as i64).contains(&point.x) && (0..self.height as i64).contains(&point.y)
+    }
+
+    fn offset(&self, point: Point2) -> Option<usize> {
+        self.contains(point)
+            .then(|| point.y as usize * self.width + point.x as usize)
+    }
+
+    fn point(&self, offset: usize) -> Point2 {
+        Point2::new((offset % self.width) as i64, (offset / self.width) as i64)
+    }
+
+    /// Returns the cell at a point, or [`None`] if the point lies outside the grid.
+    pub fn get(&self, point: Point2) -> Option<&T> {
+        self.offset(point).map(|offset| &self.cells[offset])
+    }
+
+    /// Returns the cell at a point mutably, or [`None`] if the point lies outside the grid.
+    pub fn get_mut(&mut self, point: Point2) -> Option<&mut T> {
+        self.offset(point).map(|offset| &mut self.cells[offset])
+    }
+
+    /// Iterates all points of the grid in row order.
+    pub fn points(&self) -> impl Iterator<Item = Point2> + '_ {
+        (0..self.cells.len()).map(|offset| self.point(offset))
+    }
+
+    /// Iterates all cells of the grid with their position in row order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point2, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(offset, cell)| (self.point(offset), cell))
+    }
+
+    /// Iterates all cells of the grid mutably with their position in row order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point2, &mut T)> {
+        let width = self.width;
+        self.cells
+            .iter_mut()
+            .enumerate()
+            .map(move |(offset, cell)| {
+                (
+                    Point2::new((offset % width) as i64, (offset / width) as i64),
+                    cell,
+                )
+            })
+    }
+
+    /// Iterates the orthogonal neighbours of a point that lie within the grid, clockwise starting at the top.
+    pub fn neighbours4(&self, point: Point2) -> impl Iterator<Item = Point2> + '_ {
+        Direction::ALL
+            .into_iter()
+            .map(move |dir| point + dir.offset())
+            .filter(|neighbour| self.contains(*neighbour))
+    }
+
+    /// Iterates the orthogonal and diagonal neighbours of a point that lie within the grid, clockwise starting at the top.
+    pub fn neighbours8(&self, point: Point2) -> impl Iterator<Item = Point2> + '_ {
+        Direction8::ALL
+            .into_iter()
+            .map(move |dir| point + dir.offset())
+            .filter(|neighbour| self.contains(*neighbour))
+    }
+
+    /// Returns the cells of row `y`.
+    ///
+    /// # Panics
+    /// Panics if the row lies outside the grid.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} is outside of the grid");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Iterates the rows of the grid from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics for a chunk size of zero.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Iterates the cells of column `x` from top to bottom.
+    ///
+    /// # Panics
+    /// Panics if the column lies outside the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is outside of the grid");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Iterates the diagonal running from the top left to the bottom right through a point.
+    pub fn diagonal(&self, point: Point2) -> impl Iterator<Item = (Point2, &T)> {
+        let start = if self.contains(point) {
+            let steps = point.x.min(point.y);
+            Point2::new(point.x - steps, point.y - steps)
+        } else {
+            point
+        };
+        self.ray(start, Vec2::new(1, 1))
+    }
+
+    /// Iterates the diagonal running from the top right to the bottom left through a point.
+    pub fn anti_diagonal(&self, point: Point2) -> impl Iterator<Item = (Point2, &T)> {
+        let start = if self.contains(point) {
+            let steps = (self.width as i64 - 1 - point.x).min(point.y);
+            Point2::new(point.x + steps, point.y - steps)
+        } else {
+            point
+        };
+        self.ray(start, Vec2::new(-1, 1))
+    }
+
+    /// Iterates cells from `start` in steps of `step` until the edge of the grid is reached.
+    ///
+    /// # Panics
+    /// Panics if `step` is zero.
+    pub fn ray(&self, start: Point2, step: Vec2) -> impl Iterator<Item = (Point2, &T)> {
+        assert_ne!(step, Vec2::ZERO, "a ray needs a step other than zero");
+        iter::successors(Some(start), move |p| Some(*p + step))
+            .map_while(|p| self.get(p).map(|cell| (p, cell)))
+    }
+
+    /// Returns the position of the first cell in row order that matches a predicate.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point2> {
+        self.cells
+            .iter()
+            .position(&mut predicate)
+            .map(|offset| self.point(offset))
+    }
+
+    /// Iterates the positions of all cells that match a predicate in row order.
+    pub fn positions<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Point2> + 'a {
+        self.iter()
+            .filter_map(move |(point, cell)| predicate(cell).then_some(point))
+    }
+
+    /// Creates a grid of the same size by mapping every cell with `f`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// Renders the grid with one line per row, mapping every cell to a character with `f`.
+    pub fn render(&self, mut f: impl FnMut(Point2, &T) -> char) -> String {
+        let mut s = String::with_capacity((self.width + 1) * self.height);
+        for (point, cell) in self.iter() {
+            if point.x == 0 && point.y > 0 {
+                s.push('\n');
+            }
+            s.push(f(point, cell));
+        }
+        s
+    }
+}
+
+impl<T> Index<Point2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point2) -> &Self::Output {
+        match self.offset(point) {
+            Some(offset) => &self.cells[offset],
+            None => panic!(
+                "point {point} is outside of the {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Point2> for Grid<T> {
+    fn index_mut(&mut self, point: Point2) -> &mut Self::Output {
+        match self.offset(point) {
+            Some(offset) => &mut self.cells[offset],
+            None => panic!(
+                "point {point} is outside of the {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Grid;
+    use crate::point::{Point2, Vec2};
+
+    fn example() -> Grid<char> {
+        Grid::parse("abc\ndef\nghi\njkl\n", |c| c)
+    }
+
+    #[test]
+    fn parses_grid() {
+        let grid = example();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 4);
+        assert_eq!(grid[Point2::new(2, 1)], 'f');
+        assert_eq!(grid.to_string(), "abc\ndef\nghi\njkl");
+
+        let empty = Grid::parse("", |c| c);
+        assert_eq!((empty.width(), empty.height()), (0, 0));
+        assert_eq!(empty.rows().count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_for_ragged_lines() {
+        Grid::parse("ab\nc", |c| c);
+    }
+
+    #[test]
+    fn checks_bounds() {
+        let grid = example();
+        assert!(grid.contains(Point2::new(0, 0)));
+        assert!(!grid.contains(Point2::new(3, 0)));
+        assert!(!grid.contains(Point2::new(-1, 0)));
+        assert!(!grid.contains(Point2::new(0, 4)));
+        assert_eq!(grid.get(Point2::new(0, -1)), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_when_indexing_outside() {
+        let _ = example()[Point2::new(3, 0)];
+    }
+
+    #[test]
+    fn updates_cells() {
+        let mut grid = Grid::filled(2, 2, '.');
+        grid[Point2::new(1, 0)] = '#';
+        *grid.get_mut(Point2::new(0, 1)).unwrap() = '#';
+        assert_eq!(grid.to_string(), ".#\n#.");
+    }
+
+    #[test]
+    fn returns_neighbours() {
+        let grid = example();
+        let corner: Vec<_> = grid.neighbours4(Point2::new(0, 0)).collect();
+        assert_eq!(corner, vec![Point2::new(1, 0), Point2::new(0, 1)]);
+        assert_eq!(grid.neighbours4(Point2::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8(Point2::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8(Point2::new(2, 3)).count(), 3);
+    }
+
+    #[test]
+    fn returns_views() {
+        let grid = example();
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "behk");
+        assert_eq!(grid.rows().count(), 4);
+
+        let diagonal: String = grid.diagonal(Point2::new(1, 2)).map(|(_, c)| c).collect();
+        assert_eq!(diagonal, "dhl");
+        let anti_diagonal: String = grid
+            .anti_diagonal(Point2::new(1, 2))
+            .map(|(_, c)| c)
+            .collect();
+        assert_eq!(anti_diagonal, "fhj");
+        assert_eq!(grid.diagonal(Point2::new(-1, 2)).count(), 0);
+
+        let ray: String = grid
+            .ray(Point2::new(2, 0), Vec2::new(-1, 2))
+            .map(|(_, c)| c)
+            .collect();
+        assert_eq!(ray, "ch");
+    }
+
+    #[test]
+    fn finds_cells() {
+        let grid = Grid::parse("#..\n.#.\n..#", |c| c);
+        assert_eq!(grid.find(|c| *c == '.'), Some(Point2::new(1, 0)));
+        assert_eq!(grid.find(|c| *c == 'x'), None);
+        assert_eq!(
+            grid.positions(|c| *c == '#').collect::<Vec<_>>(),
+            vec![Point2::new(0, 0), Point2::new(1, 1), Point2::new(2, 2)]
+        );
+    }
+
+    #[test]
+    fn renders_grid() {
+        let grid = Grid::parse("12\n34", |c| c.to_digit(10).unwrap());
+        assert_eq!(grid.map(|n| n * 2).to_string(), "24\n68");
+        assert_eq!(
+            grid.render(|p, n| if p.x == p.y {
+                'x'
+            } else {
+                char::from(b'0' + *n as u8)
+            }),
+            "x2\n3x"
+        );
+    }
+}
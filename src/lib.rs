@@ -1,3 +1,11 @@
+pub mod cycle;
+pub mod direction;
+pub mod equation;
+pub mod grid;
+pub mod parse;
+pub mod point;
+pub mod range_set;
+pub mod search;
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
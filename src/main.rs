@@ -1,78 +1,154 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{all, download, read, scaffold, solve, time, verify};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::PuzzleId;
 #[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::commands::solve::SolveOptions;
+    use advent_of_code::template::config::config;
+    use advent_of_code::template::output::OutputFormat;
+    use advent_of_code::template::{Day, PuzzleId, Year};
+    use std::{error::Error, process};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
             download: bool,
             overwrite: bool,
         },
         Solve {
-            day: Day,
-            release: bool,
-            dhat: bool,
-            submit: Option<u8>,
+            puzzle: PuzzleId,
+            options: SolveOptions,
         },
         All {
-            release: bool,
+            year: Year,
+            format: OutputFormat,
+            jobs: Option<usize>,
         },
         Time {
+            year: Year,
             all: bool,
             day: Option<Day>,
             store: bool,
+            compare: bool,
+            format: OutputFormat,
+            jobs: Option<usize>,
+        },
+        Verify {
+            year: Year,
+            all: bool,
+            day: Option<Day>,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    /// Read the `--year` option, falling back to the year set in `aoc.toml`.
+    fn year(args: &mut pico_args::Arguments) -> Result<Year, Box<dyn Error>> {
+        let year: Option<Year> = args.opt_value_from_str("--year")?;
+        year.or(config().year)
+            .ok_or_else(|| "no year specified. Pass `--year` or set `year` in aoc.toml.".into())
+    }
+
+    /// Read a puzzle, passed either like `2024-06` or as a day number with the year read by [`year`].
+    fn puzzle(args: &mut pico_args::Arguments) -> Result<PuzzleId, Box<dyn Error>> {
+        let year = year(args);
+        let puzzle: String = args.free_from_str()?;
+
+        if puzzle.contains('-') {
+            return Ok(puzzle.parse()?);
+        }
+
+        let day: Day = puzzle.parse()?;
+        Ok(PuzzleId::try_new(year?, day)?)
+    }
+
+    /// Read an optional day, which has to be part of the calendar of the year.
+    fn day(args: &mut pico_args::Arguments, year: Year) -> Result<Option<Day>, Box<dyn Error>> {
+        let day: Option<Day> = args.opt_free_from_str()?;
+        match day {
+            Some(day) => Ok(Some(PuzzleId::try_new(year, day)?.day)),
+            None => Ok(None),
+        }
+    }
+
+    /// Read the `--jobs` option, the number of days that run at once.
+    fn jobs(args: &mut pico_args::Arguments) -> Result<Option<usize>, Box<dyn Error>> {
+        let jobs: Option<usize> = args.opt_value_from_str(["-j", "--jobs"])?;
+        if jobs == Some(0) {
+            return Err("`--jobs` must be at least 1.".into());
+        }
+        Ok(jobs)
+    }
+
+    pub fn parse() -> Result<AppArguments, Box<dyn Error>> {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
-                release: args.contains("--release"),
+                year: year(&mut args)?,
+                format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                jobs: jobs(&mut args)?,
             },
             Some("time") => {
+                let year = year(&mut args)?;
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let compare = args.contains("--compare");
+                let format = args.opt_value_from_str("--format")?.unwrap_or_default();
+                let jobs = jobs(&mut args)?;
 
                 AppArguments::Time {
+                    year,
                     all,
-                    day: args.opt_free_from_str()?,
+                    day: day(&mut args, year)?,
                     store,
+                    compare,
+                    format,
+                    jobs,
+                }
+            }
+            Some("verify") => {
+                let year = year(&mut args)?;
+                let all = args.contains("--all");
+
+                AppArguments::Verify {
+                    year,
+                    all,
+                    day: day(&mut args, year)?,
                 }
             }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: puzzle(&mut args)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: puzzle(&mut args)?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                puzzle: puzzle(&mut args)?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+                puzzle: puzzle(&mut args)?,
+                options: SolveOptions {
+                    release: args.contains("--release"),
+                    submit_part: args.opt_value_from_str("--submit")?,
+                    dhat: args.contains("--dhat"),
+                    format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                    verbose: args.contains(["-v", "--verbose"]),
+                    visualize: args.contains("--visualize"),
+                    render: args.opt_value_from_str("--render")?,
+                },
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -102,38 +178,42 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
+            AppArguments::All { year, format, jobs } => all::handle(year, format, jobs),
+            AppArguments::Time {
+                year,
                 day,
+                all,
+                store,
+                compare,
+                format,
+                jobs,
+            } => time::handle(year, day, all, store, compare, format, jobs),
+            AppArguments::Verify { year, day, all } => verify::handle(year, day, all),
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Scaffold {
+                puzzle,
                 download,
                 overwrite,
             } => {
-                scaffold::handle(day, overwrite);
+                scaffold::handle(puzzle, overwrite);
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle);
                 }
             }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
+            AppArguments::Solve { puzzle, options } => solve::handle(puzzle, options),
             #[cfg(feature = "today")]
             AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                match PuzzleId::today() {
+                    Some(puzzle) => {
+                        scaffold::handle(puzzle, false);
+                        download::handle(puzzle);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            "`today` command can only be run on a day of this year's \
+                            advent calendar. Please use `scaffold` with a specific day."
                         );
                         process::exit(1)
                     }
@@ -0,0 +1,345 @@
+/// Parsers for shapes of puzzle input that come up again and again, built on `nom`.
+///
+/// The functions at the top of this module parse a whole input and report the line and column of invalid input.
+/// The combinators below them can be used to build parsers for other shapes, see [`run`].
+///
+/// ```
+/// # use advent_of_code::parse;
+/// let records: Vec<(u64, Vec<u64>)> = parse::records("190: 10 19\n3267: 81 40 27\n").unwrap();
+/// assert_eq!(records[1], (3267, vec![81, 40, 27]));
+///
+/// let error = parse::records::<u64, u64>("190: 10 19\n3267 81").unwrap_err();
+/// assert_eq!(error.to_string(), "line 2, column 5: expected `:`");
+/// ```
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use nom::{
+    branch::alt,
+    bytes::complete::take_till1,
+    character::complete::{
+        anychar, char, digit1, line_ending, multispace0, multispace1, one_of, space0, space1,
+    },
+    combinator::{eof, map, opt, peek, recognize},
+    error::{context, VerboseError, VerboseErrorKind},
+    multi::{many0, separated_list0, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated},
+    IResult,
+};
+
+use crate::grid::Grid;
+
+/// The result of the combinators in this module.
+pub type ParseResult<'a, T> = IResult<&'a str, T, VerboseError<&'a str>>;
+
+/// An error that points to the position of invalid input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line of the invalid input, starting at 1.
+    pub line: usize,
+    /// The column of the invalid input, starting at 1.
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    fn at(input: &str, rest: &str, message: String) -> Self {
+        let offset = input.len() - rest.len();
+        let before = &input[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before[before.rfind('\n').map_or(0, |i| i + 1)..]
+            .chars()
+            .count()
+            + 1;
+        Self {
+            line,
+            column,
+            message,
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+/// Runs `parser` over the whole input. Only whitespace may remain after it.
+pub fn run<'a, T>(
+    input: &'a str,
+    parser: impl FnMut(&'a str) -> ParseResult<'a, T>,
+) -> Result<T, ParseError> {
+    let mut parser = terminated(parser, pair(multispace0, context("end of input", eof)));
+    match parser(input) {
+        Ok((_, value)) => Ok(value),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(to_parse_error(input, e)),
+        Err(nom::Err::Incomplete(_)) => {
+            Err(ParseError::at(input, "", "unexpected end of input".into()))
+        }
+    }
+}
+
+/// Converts the innermost position of a `nom` error, described by its innermost context.
+fn to_parse_error(input: &str, e: VerboseError<&str>) -> ParseError {
+    let Some((rest, kind)) = e.errors.first() else {
+        return ParseError::at(input, input, "invalid input".into());
+    };
+    let context = e.errors.iter().find_map(|(_, kind)| match kind {
+        VerboseErrorKind::Context(context) => Some(*context),
+        _ => None,
+    });
+    let message = match (context, kind) {
+        (Some(context), _) => format!("expected {context}"),
+        (None, VerboseErrorKind::Char(c)) => format!("expected `{c}`"),
+        (None, VerboseErrorKind::Nom(kind)) => format!("invalid input ({})", kind.description()),
+        (None, VerboseErrorKind::Context(context)) => format!("expected {context}"),
+    };
+    ParseError::at(input, rest, message)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Extracts all integers from a text, ignoring anything in between. A `-` or `+` directly in front of a number is its sign.
+/// For unsigned types, a `-` is skipped like any other character, so that ranges like `1-3` can be read.
+///
+/// ```
+/// # use advent_of_code::parse;
+/// assert_eq!(parse::integers::<i64>("p=0,4 v=3,-3").unwrap(), vec![0, 4, 3, -3]);
+/// assert_eq!(parse::integers::<u32>("1-3 a").unwrap(), vec![1, 3]);
+/// ```
+pub fn integers<T: FromStr>(input: &str) -> Result<Vec<T>, ParseError> {
+    run(
+        input,
+        map(
+            many0(alt((map(loose_integer, Some), map(anychar, |_| None)))),
+            |numbers| numbers.into_iter().flatten().collect(),
+        ),
+    )
+}
+
+/// Parses lines of the shape `key: value value ...`.
+pub fn records<K: FromStr, V: FromStr>(input: &str) -> Result<Vec<(K, Vec<V>)>, ParseError> {
+    run(input, lines(record))
+}
+
+/// Parses a list of values that are separated by commas, whitespace or both.
+///
+/// ```
+/// # use advent_of_code::parse;
+/// assert_eq!(parse::list::<u8>("3, 4,5 6").unwrap(), vec![3, 4, 5, 6]);
+/// ```
+pub fn list<T: FromStr>(input: &str) -> Result<Vec<T>, ParseError> {
+    run(
+        input,
+        preceded(
+            multispace0,
+            separated_list0(
+                alt((
+                    recognize(delimited(multispace0, char(','), multispace0)),
+                    multispace1,
+                )),
+                value,
+            ),
+        ),
+    )
+}
+
+/// Parses a grid of single digits.
+pub fn digit_grid(input: &str) -> Result<Grid<u8>, ParseError> {
+    let rows = run(input, lines(digit_line))?;
+
+    let width = rows.first().map_or(0, Vec::len);
+    if let Some(y) = rows.iter().position(|row| row.len() != width) {
+        return Err(ParseError {
+            line: y + 1,
+            column: rows[y].len().min(width) + 1,
+            message: format!("expected a line of {width} digits"),
+        });
+    }
+
+    Ok(Grid::new(width, rows.len(), rows.concat()))
+}
+
+/// Splits the input into blocks that are separated by blank lines.
+///
+/// ```
+/// # use advent_of_code::parse;
+/// assert_eq!(parse::blocks("a\nb\n\nc\n"), vec!["a\nb", "c"]);
+/// ```
+pub fn blocks(input: &str) -> Vec<&str> {
+    let mut blocks = vec![];
+    let mut start = None;
+    let mut offset = 0;
+
+    for line in input.split_inclusive('\n') {
+        match (line.trim().is_empty(), start) {
+            (true, Some(s)) => {
+                blocks.push(input[s..offset].trim_end());
+                start = None;
+            }
+            (false, None) => start = Some(offset),
+            _ => {}
+        }
+        offset += line.len();
+    }
+    if let Some(s) = start {
+        blocks.push(input[s..].trim_end());
+    }
+
+    blocks
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Parses an integer with an optional sign.
+pub fn integer<T: FromStr>(input: &str) -> ParseResult<'_, T> {
+    let (rest, digits) = context("an integer", recognize(pair(opt(one_of("+-")), digit1)))(input)?;
+    match digits.trim_start_matches('+').parse() {
+        Ok(n) => Ok((rest, n)),
+        // The digits were found, so there is no point in trying other alternatives.
+        Err(_) => Err(nom::Err::Failure(VerboseError {
+            errors: vec![(input, VerboseErrorKind::Context("a number within range"))],
+        })),
+    }
+}
+
+/// Parses an integer like [`integer`], but falls back to the digits after a `-` that `T` cannot represent.
+fn loose_integer<T: FromStr>(input: &str) -> ParseResult<'_, T> {
+    match (integer(input), input.strip_prefix('-')) {
+        (Err(nom::Err::Failure(_)), Some(digits)) if "-1".parse::<T>().is_err() => integer(digits),
+        (result, _) => result,
+    }
+}
+
+/// Parses a word that contains no whitespace, commas or colons with [`FromStr`].
+pub fn value<T: FromStr>(input: &str) -> ParseResult<'_, T> {
+    let (rest, word) = context(
+        "a value",
+        take_till1(|c: char| c.is_whitespace() || c == ',' || c == ':'),
+    )(input)?;
+    match word.parse() {
+        Ok(value) => Ok((rest, value)),
+        Err(_) => Err(nom::Err::Failure(VerboseError {
+            errors: vec![(input, VerboseErrorKind::Context("a valid value"))],
+        })),
+    }
+}
+
+/// Parses a line of the shape `key: value value ...`.
+pub fn record<K: FromStr, V: FromStr>(input: &str) -> ParseResult<'_, (K, Vec<V>)> {
+    separated_pair(
+        value,
+        pair(context("`:`", char(':')), space0),
+        separated_list1(space1, value),
+    )(input)
+}
+
+/// Applies `parser` to every line until only whitespace remains.
+///
+/// Unlike `separated_list1`, an error on any line is reported instead of ending the list early.
+pub fn lines<'a, T>(
+    mut parser: impl FnMut(&'a str) -> ParseResult<'a, T>,
+) -> impl FnMut(&'a str) -> ParseResult<'a, Vec<T>> {
+    move |mut input| {
+        let mut values = vec![];
+        loop {
+            let (rest, value) = parser(input)?;
+            values.push(value);
+            match line_ending::<_, VerboseError<&str>>(rest) {
+                Ok((next, _)) if !next.trim().is_empty() => input = next,
+                _ => return Ok((rest, values)),
+            }
+        }
+    }
+}
+
+fn digit_line(input: &str) -> ParseResult<'_, Vec<u8>> {
+    let (rest, digits) =
+        context("a digit", terminated(digit1, peek(alt((line_ending, eof)))))(input)?;
+    Ok((rest, digits.bytes().map(|b| b - b'0').collect()))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{blocks, digit_grid, integers, list, records};
+    use crate::point::Point2;
+
+    #[test]
+    fn extracts_integers() {
+        assert_eq!(
+            integers::<i64>("Button A: X+94, Y=-34").unwrap(),
+            vec![94, -34]
+        );
+        assert_eq!(integers::<i64>("a-b 3--4").unwrap(), vec![3, -4]);
+        assert_eq!(integers::<u8>("").unwrap(), vec![]);
+        assert_eq!(integers::<u32>("1-3").unwrap(), vec![1, 3]);
+        assert_eq!(integers::<u32>("a-3").unwrap(), vec![3]);
+
+        let error = integers::<i8>("-200").unwrap_err();
+        assert_eq!(error.column, 1);
+
+        let error = integers::<u8>("1 2\n3 300").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.message, "expected a number within range");
+    }
+
+    #[test]
+    fn parses_records() {
+        let parsed: Vec<(u64, Vec<u64>)> = records("190: 10 19\n3267: 81 40 27\n").unwrap();
+        assert_eq!(parsed, vec![(190, vec![10, 19]), (3267, vec![81, 40, 27])]);
+
+        let named: Vec<(String, Vec<String>)> = records("aaa: you hhh\nyou: bbb").unwrap();
+        assert_eq!(named[0], ("aaa".into(), vec!["you".into(), "hhh".into()]));
+
+        let error = records::<u64, u64>("1: 2\n3: x").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 4: expected a valid value"
+        );
+    }
+
+    #[test]
+    fn parses_lists() {
+        assert_eq!(list::<i32>("1,2,-3").unwrap(), vec![1, 2, -3]);
+        assert_eq!(list::<i32>("1 2  3\n4").unwrap(), vec![1, 2, 3, 4]);
+        assert_eq!(list::<i32>("1, 2 ,3\n").unwrap(), vec![1, 2, 3]);
+        assert_eq!(list::<i32>("").unwrap(), vec![]);
+
+        let error = list::<i32>("1,2,a").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 5));
+
+        let error = records::<u64, u64>("1: 2\n3 4\n5: 6").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 2: expected `:`");
+    }
+
+    #[test]
+    fn parses_digit_grids() {
+        let grid = digit_grid("012\n345\n").unwrap();
+        assert_eq!(grid[Point2::new(1, 1)], 4);
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+
+        let error = digit_grid("012\n3x5").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 2: expected a digit");
+
+        let error = digit_grid("012\n34").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected a line of 3 digits"
+        );
+    }
+
+    #[test]
+    fn splits_blocks() {
+        assert_eq!(blocks("a\nb\n\nc\n"), vec!["a\nb", "c"]);
+        assert_eq!(blocks("\na\r\n\r\n\r\nb"), vec!["a", "b"]);
+        assert!(blocks("").is_empty());
+    }
+}
@@ -0,0 +1,235 @@
+/// Positions and offsets on a two-dimensional grid.
+///
+/// `x` grows to the right and `y` grows downwards, matching the order in which puzzle inputs are read.
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+use num::Integer;
+
+/// A position on a two-dimensional grid.
+///
+/// Moving a point by a [`Vec2`] yields another point, subtracting two points yields the [`Vec2`] between them.
+///
+/// ```
+/// # use advent_of_code::point::{Point2, Vec2};
+/// let a = Point2::new(1, 2);
+/// let b = Point2::new(4, 6);
+/// assert_eq!(b - a, Vec2::new(3, 4));
+/// assert_eq!(a + Vec2::new(3, 4), b);
+/// assert_eq!(a.manhattan(b), 7);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point2 {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// The taxicab distance to another point.
+    pub fn manhattan(self, other: Self) -> u64 {
+        (other - self).manhattan()
+    }
+
+    /// The distance to another point when diagonal steps are allowed.
+    pub fn chebyshev(self, other: Self) -> u64 {
+        (other - self).chebyshev()
+    }
+}
+
+impl From<(i64, i64)> for Point2 {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Display for Point2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Add<Vec2> for Point2 {
+    type Output = Self;
+
+    fn add(self, rhs: Vec2) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign<Vec2> for Point2 {
+    fn add_assign(&mut self, rhs: Vec2) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Vec2> for Point2 {
+    type Output = Self;
+
+    fn sub(self, rhs: Vec2) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign<Vec2> for Point2 {
+    fn sub_assign(&mut self, rhs: Vec2) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub for Point2 {
+    type Output = Vec2;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vec2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An offset between two [`Point2`]s.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Vec2 {
+    pub const ZERO: Self = Self::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// The length of the vector when moving along the axes.
+    pub fn manhattan(self) -> u64 {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+
+    /// The length of the vector when diagonal steps are allowed.
+    pub fn chebyshev(self) -> u64 {
+        self.x.unsigned_abs().max(self.y.unsigned_abs())
+    }
+
+    /// The shortest vector with integer components that points in the same direction.
+    ///
+    /// ```
+    /// # use advent_of_code::point::Vec2;
+    /// assert_eq!(Vec2::new(4, -6).reduced(), Vec2::new(2, -3));
+    /// ```
+    pub fn reduced(self) -> Self {
+        match self.x.gcd(&self.y) {
+            0 => self,
+            gcd => Self::new(self.x / gcd, self.y / gcd),
+        }
+    }
+}
+
+impl From<(i64, i64)> for Vec2 {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Display for Vec2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<{}, {}>", self.x, self.y)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Vec2 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Vec2 {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Point2, Vec2};
+
+    #[test]
+    fn moves_points() {
+        let mut p = Point2::new(3, 4);
+        assert_eq!(p + Vec2::new(1, -1), Point2::new(4, 3));
+        assert_eq!(p - Vec2::new(1, -1), Point2::new(2, 5));
+        assert_eq!(p - Point2::ORIGIN, Vec2::new(3, 4));
+
+        p += Vec2::new(2, 2);
+        p -= Vec2::new(1, 0);
+        assert_eq!(p, Point2::new(4, 6));
+    }
+
+    #[test]
+    fn combines_vectors() {
+        let v = Vec2::new(2, -3);
+        assert_eq!(v + Vec2::new(1, 1), Vec2::new(3, -2));
+        assert_eq!(v - Vec2::new(1, 1), Vec2::new(1, -4));
+        assert_eq!(-v, Vec2::new(-2, 3));
+        assert_eq!(v * 3, Vec2::new(6, -9));
+    }
+
+    #[test]
+    fn measures_distances() {
+        let a = Point2::new(-1, 2);
+        let b = Point2::new(3, -5);
+        assert_eq!(a.manhattan(b), 11);
+        assert_eq!(b.manhattan(a), 11);
+        assert_eq!(a.chebyshev(b), 7);
+        assert_eq!(Vec2::new(-4, 1).chebyshev(), 4);
+    }
+
+    #[test]
+    fn reduces_vectors() {
+        assert_eq!(Vec2::new(4, -6).reduced(), Vec2::new(2, -3));
+        assert_eq!(Vec2::new(-3, 0).reduced(), Vec2::new(-1, 0));
+        assert_eq!(Vec2::new(5, 7).reduced(), Vec2::new(5, 7));
+        assert_eq!(Vec2::ZERO.reduced(), Vec2::ZERO);
+    }
+}
@@ -0,0 +1,298 @@
+/// Interval arithmetic for puzzles about ranges of numbers, like free space on a disk or ranges that get split.
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Range,
+};
+
+/// A set of integers that is stored as sorted, disjoint ranges.
+///
+/// Overlapping and adjacent ranges are merged on insertion, removing a range splits the ranges it cuts through.
+///
+/// ```
+/// # use advent_of_code::range_set::RangeSet;
+/// let mut set = RangeSet::from_iter([0..4, 6..8]);
+/// set.insert(4..5);
+/// set.remove(1..2);
+/// assert_eq!(set.iter().collect::<Vec<_>>(), vec![0..1, 2..5, 6..8]);
+/// assert_eq!(set.len(), 6);
+/// assert_eq!(set.first_fit(3), Some(2..5));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RangeSet {
+    /// The end of each range, keyed by its start.
+    ranges: BTreeMap<i64, i64>,
+    /// The starts of all ranges, keyed by their length. Used to find ranges of a minimum length.
+    by_len: BTreeMap<u64, BTreeSet<i64>>,
+}
+
+impl RangeSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn add_span(&mut self, start: i64, end: i64) {
+        self.ranges.insert(start, end);
+        self.by_len
+            .entry(end.abs_diff(start))
+            .or_default()
+            .insert(start);
+    }
+
+    fn remove_span(&mut self, start: i64) -> i64 {
+        let end = self
+            .ranges
+            .remove(&start)
+            .expect("range to be part of the set");
+        let len = end.abs_diff(start);
+        if let Some(starts) = self.by_len.get_mut(&len) {
+            starts.remove(&start);
+            if starts.is_empty() {
+                self.by_len.remove(&len);
+            }
+        }
+        end
+    }
+
+    /// The ranges that overlap `range`, as `(start, end)` pairs.
+    fn overlapping(&self, range: &Range<i64>) -> Vec<(i64, i64)> {
+        let before = self
+            .ranges
+            .range(..range.start)
+            .next_back()
+            .filter(|(_, end)| **end > range.start);
+        before
+            .into_iter()
+            .chain(self.ranges.range(range.start..range.end))
+            .map(|(start, end)| (*start, *end))
+            .collect()
+    }
+
+    /// Adds all values of `range` to the set.
+    pub fn insert(&mut self, range: Range<i64>) {
+        if range.is_empty() {
+            return;
+        }
+        let (mut start, mut end) = (range.start, range.end);
+
+        if let Some((&s, &e)) = self.ranges.range(..start).next_back() {
+            if e >= start {
+                start = s;
+                end = end.max(self.remove_span(s));
+            }
+        }
+        let merged: Vec<i64> = self.ranges.range(start..=end).map(|(s, _)| *s).collect();
+        for s in merged {
+            end = end.max(self.remove_span(s));
+        }
+
+        self.add_span(start, end);
+    }
+
+    /// Removes all values of `range` from the set.
+    pub fn remove(&mut self, range: Range<i64>) {
+        if range.is_empty() {
+            return;
+        }
+        for (start, end) in self.overlapping(&range) {
+            self.remove_span(start);
+            if start < range.start {
+                self.add_span(start, range.start);
+            }
+            if end > range.end {
+                self.add_span(range.end, end);
+            }
+        }
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.ranges
+            .range(..=value)
+            .next_back()
+            .is_some_and(|(_, end)| value < *end)
+    }
+
+    /// The number of values in the set.
+    pub fn len(&self) -> u64 {
+        self.ranges
+            .iter()
+            .map(|(start, end)| end.abs_diff(*start))
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Iterates the disjoint ranges of the set in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Range<i64>> + '_ {
+        self.ranges.iter().map(|(start, end)| *start..*end)
+    }
+
+    /// The number of values of `range` that are part of the set.
+    pub fn overlap(&self, range: Range<i64>) -> u64 {
+        self.overlapping(&range)
+            .into_iter()
+            .map(|(start, end)| end.min(range.end).abs_diff(start.max(range.start)))
+            .sum()
+    }
+
+    /// Returns the leftmost range of the set that holds at least `len` values.
+    ///
+    /// Only looks at one range per distinct length, which makes this fast for sets that model e.g. free space.
+    pub fn first_fit(&self, len: u64) -> Option<Range<i64>> {
+        self.by_len
+            .range(len..)
+            .filter_map(|(_, starts)| starts.first())
+            .min()
+            .map(|start| *start..self.ranges[start])
+    }
+
+    /// Iterates the ranges within `bounds` that are not part of the set.
+    pub fn gaps(&self, bounds: Range<i64>) -> impl Iterator<Item = Range<i64>> + '_ {
+        let Range {
+            start: mut cursor,
+            end: last,
+        } = bounds;
+        self.overlapping(&bounds)
+            .into_iter()
+            .chain([(last, last)])
+            .filter_map(move |(start, end)| {
+                let gap = cursor..start;
+                cursor = cursor.max(end);
+                (!gap.is_empty()).then_some(gap)
+            })
+    }
+
+    /// The values that are part of both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        other
+            .iter()
+            .flat_map(|range| {
+                self.overlapping(&range)
+                    .into_iter()
+                    .map(move |(start, end)| start.max(range.start)..end.min(range.end))
+            })
+            .collect()
+    }
+
+    /// The values that are part of either set.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.extend(other.iter());
+        union
+    }
+
+    /// The values of this set that are not part of `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for range in other.iter() {
+            difference.remove(range);
+        }
+        difference
+    }
+}
+
+impl Extend<Range<i64>> for RangeSet {
+    fn extend<I: IntoIterator<Item = Range<i64>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl FromIterator<Range<i64>> for RangeSet {
+    fn from_iter<I: IntoIterator<Item = Range<i64>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::RangeSet;
+
+    fn ranges(set: &RangeSet) -> Vec<std::ops::Range<i64>> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn merges_ranges_on_insert() {
+        let mut set = RangeSet::from_iter([0..2, 5..7, 10..12]);
+        assert_eq!(ranges(&set), vec![0..2, 5..7, 10..12]);
+
+        set.insert(2..3);
+        assert_eq!(ranges(&set), vec![0..3, 5..7, 10..12]);
+
+        set.insert(4..11);
+        assert_eq!(ranges(&set), vec![0..3, 4..12]);
+
+        set.insert(-5..20);
+        assert_eq!(ranges(&set), vec![-5..20]);
+
+        set.insert(3..3);
+        assert_eq!(set.len(), 25);
+    }
+
+    #[test]
+    fn splits_ranges_on_remove() {
+        let mut set = RangeSet::from_iter([0..10, 20..30]);
+        set.remove(3..5);
+        assert_eq!(ranges(&set), vec![0..3, 5..10, 20..30]);
+
+        set.remove(8..25);
+        assert_eq!(ranges(&set), vec![0..3, 5..8, 25..30]);
+
+        set.remove(-10..100);
+        assert!(set.is_empty());
+        assert_eq!(set, RangeSet::new());
+    }
+
+    #[test]
+    fn checks_membership_and_overlap() {
+        let set = RangeSet::from_iter([0..3, 5..8]);
+        assert!(set.contains(0));
+        assert!(!set.contains(3));
+        assert!(set.contains(7));
+        assert!(!set.contains(-1));
+        assert_eq!(set.overlap(2..6), 2);
+        assert_eq!(set.overlap(10..20), 0);
+    }
+
+    #[test]
+    fn finds_first_fit() {
+        let mut set = RangeSet::from_iter([0..1, 3..5, 8..12, 20..22]);
+        assert_eq!(set.first_fit(1), Some(0..1));
+        assert_eq!(set.first_fit(2), Some(3..5));
+        assert_eq!(set.first_fit(3), Some(8..12));
+        assert_eq!(set.first_fit(5), None);
+
+        set.remove(3..4);
+        assert_eq!(set.first_fit(2), Some(8..12));
+        set.insert(2..4);
+        assert_eq!(set.first_fit(3), Some(2..5));
+    }
+
+    #[test]
+    fn finds_gaps() {
+        let set = RangeSet::from_iter([0..3, 5..8]);
+        assert_eq!(
+            set.gaps(-2..10).collect::<Vec<_>>(),
+            vec![-2..0, 3..5, 8..10]
+        );
+        assert_eq!(set.gaps(1..6).collect::<Vec<_>>(), vec![3..5]);
+        assert_eq!(set.gaps(0..3).count(), 0);
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a = RangeSet::from_iter([0..5, 10..15]);
+        let b = RangeSet::from_iter([3..12]);
+        assert_eq!(ranges(&a.intersection(&b)), vec![3..5, 10..12]);
+        assert_eq!(ranges(&a.union(&b)), vec![0..15]);
+        assert_eq!(ranges(&a.difference(&b)), vec![0..3, 12..15]);
+        assert_eq!(ranges(&b.difference(&a)), vec![5..10]);
+    }
+}
@@ -0,0 +1,349 @@
+/// Generic graph searches over states that are connected by a neighbour function.
+///
+/// States can be anything hashable, e.g. a [`Point2`](crate::point::Point2) on a grid or a tuple of a position and a direction.
+///
+/// ```
+/// # use advent_of_code::{grid::Grid, point::Point2, search};
+/// let grid = Grid::parse("..#\n#..\n...", |c| c == '#');
+/// let path = search::bfs(
+///     Point2::new(0, 0),
+///     |p| grid.neighbours4(*p).filter(|n| !grid[*n]),
+///     |p| *p == Point2::new(2, 2),
+/// );
+/// assert_eq!(path.map(|path| path.len() - 1), Some(4));
+/// ```
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+use num::Zero;
+
+/// Collects every state that is reachable from `start`, including `start` itself.
+pub fn reachable<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> HashSet<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    distances(start, &mut neighbours).into_keys().collect()
+}
+
+/// Computes the number of steps from `start` to every reachable state in breadth first order.
+pub fn distances<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in neighbours(&node) {
+            if let Entry::Vacant(entry) = distances.entry(next.clone()) {
+                entry.insert(distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+/// Visits every state that is reachable from `start` in depth first order.
+pub fn dfs<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = HashSet::new();
+    let mut order = vec![];
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+        let mut next: Vec<_> = neighbours(&node)
+            .into_iter()
+            .filter(|next| !visited.contains(next))
+            .collect();
+        // Reverse so that neighbours are visited in the order they are returned.
+        next.reverse();
+        stack.extend(next);
+        order.push(node);
+    }
+
+    order
+}
+
+/// Finds a path with the fewest steps from `start` to a state that satisfies `is_goal`.
+///
+/// The path includes both `start` and the goal.
+pub fn bfs<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents: HashMap<N, Option<N>> = HashMap::from([(start.clone(), None)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            return Some(reconstruct_path(node, |n| parents[n].clone()));
+        }
+        for next in neighbours(&node) {
+            if let Entry::Vacant(entry) = parents.entry(next.clone()) {
+                entry.insert(Some(node.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Finds a path with the lowest cost from `start` to a state that satisfies `is_goal`.
+///
+/// `neighbours` returns the next states together with the cost of moving there. Costs must not be negative.
+/// Returns the path, including both `start` and the goal, and its total cost.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, neighbours, |_| C::zero(), is_goal)
+}
+
+/// Finds a path with the lowest cost like [`dijkstra`], guided by a `heuristic`.
+///
+/// The heuristic estimates the remaining cost to a goal. It must never overestimate it, otherwise the path may not be the cheapest.
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    I: IntoIterator<Item = (N, C)>,
+{
+    // The heap holds indices into `nodes`, so that states do not need to implement `Ord`.
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::zero(), 0))]);
+    let mut best: HashMap<N, (Option<N>, C)> = HashMap::from([(start.clone(), (None, C::zero()))]);
+    let mut nodes = vec![start];
+
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        let node = nodes[index].clone();
+        if cost > best[&node].1 {
+            continue;
+        }
+        if is_goal(&node) {
+            return Some((reconstruct_path(node, |n| best[n].0.clone()), cost));
+        }
+        for (next, step) in neighbours(&node) {
+            let next_cost = cost + step;
+            match best.entry(next.clone()) {
+                Entry::Occupied(entry) if entry.get().1 <= next_cost => continue,
+                Entry::Occupied(mut entry) => {
+                    entry.insert((Some(node.clone()), next_cost));
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert((Some(node.clone()), next_cost));
+                }
+            }
+            heap.push(Reverse((
+                next_cost + heuristic(&next),
+                next_cost,
+                nodes.len(),
+            )));
+            nodes.push(next);
+        }
+    }
+
+    None
+}
+
+/// Counts the distinct paths from `start` to states that satisfy `is_goal`.
+///
+/// Paths end at the first goal they reach. The states must form a directed acyclic graph, counts of shared sub-paths are memoised.
+pub fn count_paths<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> u64
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    fn count<N, I>(
+        node: &N,
+        neighbours: &mut impl FnMut(&N) -> I,
+        is_goal: &mut impl FnMut(&N) -> bool,
+        memo: &mut HashMap<N, u64>,
+    ) -> u64
+    where
+        N: Clone + Eq + Hash,
+        I: IntoIterator<Item = N>,
+    {
+        if is_goal(node) {
+            return 1;
+        }
+        if let Some(paths) = memo.get(node) {
+            return *paths;
+        }
+        let paths = neighbours(node)
+            .into_iter()
+            .map(|next| count(&next, neighbours, is_goal, memo))
+            .sum();
+        memo.insert(node.clone(), paths);
+        paths
+    }
+
+    count(&start, &mut neighbours, &mut is_goal, &mut HashMap::new())
+}
+
+/// Follows `parent` from `goal` back to a state without a parent and returns the path in forward order.
+pub fn reconstruct_path<N>(goal: N, mut parent: impl FnMut(&N) -> Option<N>) -> Vec<N> {
+    let mut path = vec![];
+    let mut node = Some(goal);
+    while let Some(current) = node {
+        node = parent(&current);
+        path.push(current);
+    }
+    path.reverse();
+    path
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{astar, bfs, count_paths, dfs, dijkstra, distances, reachable, reconstruct_path};
+    use crate::{grid::Grid, point::Point2};
+
+    fn maze() -> Grid<bool> {
+        Grid::parse(".....\n.###.\n...#.\n.#...\n.#.#.", |c| c == '#')
+    }
+
+    fn open_neighbours(grid: &Grid<bool>, p: Point2) -> impl Iterator<Item = Point2> + '_ {
+        grid.neighbours4(p).filter(|n| !grid[*n])
+    }
+
+    #[test]
+    fn finds_reachable_states() {
+        let grid = Grid::parse("..#.\n..#.\n###.", |c| c == '#');
+        let reached = reachable(Point2::new(0, 0), |p| open_neighbours(&grid, *p));
+        assert_eq!(reached.len(), 4);
+        assert!(!reached.contains(&Point2::new(3, 0)));
+
+        let steps = distances(Point2::new(0, 0), |p| open_neighbours(&grid, *p));
+        assert_eq!(steps[&Point2::new(1, 1)], 2);
+    }
+
+    #[test]
+    fn visits_depth_first() {
+        let edges = HashMap::from([(1, vec![2, 3]), (2, vec![4]), (3, vec![4]), (4, vec![])]);
+        assert_eq!(dfs(1, |n| edges[n].clone()), vec![1, 2, 4, 3]);
+    }
+
+    #[test]
+    fn finds_shortest_path_on_grid() {
+        let grid = maze();
+        let goal = Point2::new(4, 4);
+        let path = bfs(
+            Point2::new(0, 0),
+            |p| open_neighbours(&grid, *p),
+            |p| *p == goal,
+        )
+        .unwrap();
+        assert_eq!(path.len(), 9);
+        assert_eq!(path.first(), Some(&Point2::new(0, 0)));
+        assert_eq!(path.last(), Some(&goal));
+        assert!(path.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+
+        let walled = Grid::parse(".#\n#.", |c| c == '#');
+        assert_eq!(
+            bfs(
+                Point2::new(0, 0),
+                |p| open_neighbours(&walled, *p),
+                |p| *p == Point2::new(1, 1)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn finds_cheapest_path() {
+        // Going through `b` takes more steps but is cheaper.
+        let edges: HashMap<char, Vec<(char, u32)>> = HashMap::from([
+            ('a', vec![('b', 1), ('d', 10)]),
+            ('b', vec![('c', 1)]),
+            ('c', vec![('d', 1)]),
+            ('d', vec![]),
+        ]);
+        let result = dijkstra('a', |n| edges[n].clone(), |n| *n == 'd');
+        assert_eq!(result, Some((vec!['a', 'b', 'c', 'd'], 3)));
+        assert_eq!(dijkstra('d', |n| edges[n].clone(), |n| *n == 'a'), None);
+    }
+
+    #[test]
+    fn finds_cheapest_path_with_heuristic() {
+        let grid = maze();
+        let goal = Point2::new(4, 4);
+        let (path, cost) = astar(
+            Point2::new(0, 0),
+            |p| open_neighbours(&grid, *p).map(|n| (n, 1u64)),
+            |p| p.manhattan(goal),
+            |p| *p == goal,
+        )
+        .unwrap();
+        assert_eq!(cost, 8);
+        assert_eq!(path.len(), 9);
+    }
+
+    #[test]
+    fn counts_paths() {
+        let grid = Grid::filled(3, 3, ());
+        let paths = count_paths(
+            Point2::new(0, 0),
+            |p| {
+                [Point2::new(p.x + 1, p.y), Point2::new(p.x, p.y + 1)]
+                    .into_iter()
+                    .filter(|n| grid.contains(*n))
+            },
+            |p| *p == Point2::new(2, 2),
+        );
+        assert_eq!(paths, 6);
+
+        let edges = HashMap::from([
+            (1, vec![2, 3]),
+            (2, vec![4]),
+            (3, vec![4, 5]),
+            (4, vec![]),
+            (5, vec![]),
+        ]);
+        assert_eq!(count_paths(1, |n| edges[n].clone(), |n| *n == 4), 2);
+    }
+
+    #[test]
+    fn reconstructs_paths() {
+        let parents = HashMap::from([(3, 2), (2, 1)]);
+        assert_eq!(
+            reconstruct_path(3, |n| parents.get(n).copied()),
+            vec![1, 2, 3]
+        );
+    }
+}
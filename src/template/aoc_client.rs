@@ -0,0 +1,565 @@
+/// Native client for the Advent of Code website.
+/// Downloads inputs and puzzle descriptions, and submits answers on behalf of the session cookie's owner.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::template::config::config;
+use crate::template::PuzzleId;
+
+const USER_AGENT: &str = "advent_of_code template (github.com/fspoettel/advent-of-code-rust)";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    /// No session cookie was found in the environment or in the session file.
+    MissingSession,
+    /// The session cookie was rejected, e.g. because it expired.
+    Unauthorized,
+    /// The puzzle for the requested day has not been released yet.
+    NotYetUnlocked,
+    /// The server responded with an unexpected status code.
+    BadStatus(u16),
+    /// The server could not be reached.
+    Transport(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set ADVENT_OF_CODE_SESSION or create the session file configured in aoc.toml."
+            ),
+            AocClientError::Unauthorized => {
+                write!(f, "the session cookie was rejected. It might have expired.")
+            }
+            AocClientError::NotYetUnlocked => write!(f, "this puzzle has not been unlocked yet."),
+            AocClientError::BadStatus(status) => {
+                write!(f, "adventofcode.com responded with status {status}.")
+            }
+            AocClientError::Transport(e) => write!(f, "could not reach adventofcode.com: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(400 | 401 | 403, _) => AocClientError::Unauthorized,
+            ureq::Error::Status(404, _) => AocClientError::NotYetUnlocked,
+            ureq::Error::Status(status, _) => AocClientError::BadStatus(status),
+            ureq::Error::Transport(e) => AocClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.trim().into(),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    /// Create a client from the project configuration:
+    ///  1. the base URL is read from `aoc.toml`, defaulting to the Advent of Code website.
+    ///  2. the session cookie is read from `ADVENT_OF_CODE_SESSION` or from the session file set in `aoc.toml`.
+    pub fn from_config() -> Result<Self, AocClientError> {
+        let session = read_session().ok_or(AocClientError::MissingSession)?;
+        Ok(Self::new(&config().base_url, &session))
+    }
+
+    /// Fetch the input of a puzzle.
+    pub fn get_input(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let url = format!("{}/input", self.puzzle_url(puzzle));
+        Ok(self.get(&url)?.into_string()?)
+    }
+
+    /// Fetch the description of a puzzle, converted to markdown.
+    pub fn get_puzzle(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let html = self.get(&self.puzzle_url(puzzle))?.into_string()?;
+
+        Ok(articles(&html)
+            .iter()
+            .map(|article| html_to_markdown(article))
+            .collect::<Vec<_>>()
+            .join("\n\n"))
+    }
+
+    /// Submit an answer for one part of a puzzle and parse the server's verdict.
+    pub fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<Outcome, AocClientError> {
+        let url = format!("{}/answer", self.puzzle_url(puzzle));
+
+        let html = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?
+            .into_string()?;
+
+        let message = articles(&html)
+            .first()
+            .map(|article| html_to_markdown(article))
+            .unwrap_or_default();
+
+        Ok(Outcome::from_message(&message))
+    }
+
+    fn get(&self, url: &str) -> Result<ureq::Response, AocClientError> {
+        Ok(self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()?)
+    }
+
+    fn puzzle_url(&self, puzzle: PuzzleId) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url,
+            puzzle.year,
+            puzzle.day.into_inner()
+        )
+    }
+}
+
+/// The verdict of the server on a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    /// The answer is wrong, and the server did not say in which direction.
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// The part was already solved, so the answer was not checked.
+    AlreadySolved,
+    /// An answer was submitted too recently. Contains the remaining wait time, if the server named it.
+    RateLimited(Option<Duration>),
+}
+
+impl Outcome {
+    /// Parse the message the server responds with after a submission.
+    pub fn from_message(message: &str) -> Self {
+        if message.contains("That's the right answer") {
+            Outcome::Correct
+        } else if message.contains("You gave an answer too recently") {
+            Outcome::RateLimited(parse_wait_time(message))
+        } else if message.contains("You don't seem to be solving the right level") {
+            Outcome::AlreadySolved
+        } else if message.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if message.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Incorrect
+        }
+    }
+
+    /// Whether the outcome means the answer is wrong.
+    pub fn is_wrong(&self) -> bool {
+        matches!(
+            self,
+            Outcome::Incorrect | Outcome::TooHigh | Outcome::TooLow
+        )
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "That's the right answer!"),
+            Outcome::Incorrect => write!(f, "That's not the right answer."),
+            Outcome::TooHigh => write!(f, "That's not the right answer, it is too high."),
+            Outcome::TooLow => write!(f, "That's not the right answer, it is too low."),
+            Outcome::AlreadySolved => write!(f, "This part was already solved."),
+            Outcome::RateLimited(Some(wait)) => write!(
+                f,
+                "You gave an answer too recently. Wait {}s before trying again.",
+                wait.as_secs()
+            ),
+            Outcome::RateLimited(None) => write!(f, "You gave an answer too recently."),
+        }
+    }
+}
+
+/// Parse a wait time like "You have 1m 5s left to wait." from a server message.
+fn parse_wait_time(message: &str) -> Option<Duration> {
+    let start = message.find("You have ")? + "You have ".len();
+    let end = start + message[start..].find(" left to wait")?;
+
+    message[start..end]
+        .split_whitespace()
+        .map(|token| {
+            let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+            match unit {
+                "h" => Some(value * 3600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Download the input and description of a puzzle to the `data` directory.
+pub fn download(puzzle: PuzzleId) -> Result<(), AocClientError> {
+    let client = AocClient::from_config()?;
+
+    let input_path = get_input_path(puzzle);
+    let puzzle_path = get_puzzle_path(puzzle);
+
+    write_file(&input_path, &client.get_input(puzzle)?)?;
+    write_file(&puzzle_path, &client.get_puzzle(puzzle)?)?;
+
+    println!("---");
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
+    Ok(())
+}
+
+/// Fetch the description of a puzzle, store it in the `data` directory and print it.
+pub fn read(puzzle: PuzzleId) -> Result<(), AocClientError> {
+    let client = AocClient::from_config()?;
+    let description = client.get_puzzle(puzzle)?;
+    write_file(&get_puzzle_path(puzzle), &description)?;
+    println!("{description}");
+    Ok(())
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), io::Error> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)
+}
+
+fn get_input_path(puzzle: PuzzleId) -> PathBuf {
+    config()
+        .data_dir
+        .join(puzzle.year.to_string())
+        .join("inputs")
+        .join(format!("{}.txt", puzzle.day))
+}
+
+fn get_puzzle_path(puzzle: PuzzleId) -> PathBuf {
+    config()
+        .data_dir
+        .join(puzzle.year.to_string())
+        .join("puzzles")
+        .join(format!("{}.md", puzzle.day))
+}
+
+fn read_session() -> Option<String> {
+    if let Ok(session) = env::var("ADVENT_OF_CODE_SESSION") {
+        return Some(session);
+    }
+
+    let path = config().session_file.as_ref()?;
+    fs::read_to_string(path)
+        .ok()
+        .filter(|x| !x.trim().is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Extract the inner HTML of all `<article>` elements of a page.
+fn articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(open_end) = rest[start..].find('>') else {
+            break;
+        };
+        let content_start = start + open_end + 1;
+        let Some(len) = rest[content_start..].find("</article>") else {
+            break;
+        };
+        articles.push(&rest[content_start..content_start + len]);
+        rest = &rest[content_start + len..];
+    }
+
+    articles
+}
+
+/// Convert the subset of HTML used in puzzle descriptions to markdown.
+fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut in_pre = false;
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        out.push_str(&rest[..start]);
+
+        let Some(len) = rest[start..].find('>') else {
+            rest = &rest[start..];
+            break;
+        };
+
+        let tag = &rest[start + 1..start + len];
+        let name = tag
+            .split(|c: char| c.is_whitespace())
+            .next()
+            .unwrap_or_default();
+
+        match name {
+            "h2" => out.push_str("## "),
+            "/h2" | "/p" | "/ul" => out.push_str("\n\n"),
+            "pre" => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            "/pre" => {
+                in_pre = false;
+                out.push_str("```\n\n");
+            }
+            "code" | "/code" if !in_pre => out.push('`'),
+            "em" | "/em" => out.push('*'),
+            "li" => out.push_str("- "),
+            "/li" => out.push('\n'),
+            _ => {}
+        }
+
+        rest = &rest[start + len + 1..];
+    }
+
+    out.push_str(rest);
+
+    let text = out
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+
+    let mut markdown = String::new();
+    for line in text.trim().lines() {
+        // NOTE: collapse runs of blank lines that result from nested block elements.
+        if line.trim().is_empty() && markdown.ends_with("\n\n") {
+            continue;
+        }
+        markdown.push_str(line.trim_end());
+        markdown.push('\n');
+    }
+
+    markdown.trim_end().to_string()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    use std::time::Duration;
+
+    use super::{articles, html_to_markdown, AocClient, AocClientError, Outcome};
+    use crate::puzzle;
+
+    /// Serve a single canned response on a local port, sending the received request head back to the test.
+    fn serve_once(status: u16, body: &'static str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut head = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                if line == "\r\n" {
+                    break;
+                }
+                head.push_str(&line);
+            }
+
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+            head.push_str(&String::from_utf8_lossy(&request_body));
+            tx.send(head).unwrap();
+
+            write!(
+                stream,
+                "HTTP/1.1 {status} Status\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+        });
+
+        (base_url, rx)
+    }
+
+    #[test]
+    fn downloads_input() {
+        let (base_url, rx) = serve_once(200, "1 2 3\n");
+        let client = AocClient::new(&base_url, "secret");
+
+        assert_eq!(client.get_input(puzzle!(2024, 6)).unwrap(), "1 2 3\n");
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("GET /2024/day/6/input "));
+        assert!(request.contains("Cookie: session=secret"));
+    }
+
+    #[test]
+    fn converts_puzzle_to_markdown() {
+        let (base_url, _rx) = serve_once(
+            200,
+            "<main><article class=\"day-desc\"><h2>--- Day 6 ---</h2><p>Some <em>text</em> &amp; <code>code</code>.</p><pre><code>#..\n.^.\n</code></pre></article></main>",
+        );
+        let client = AocClient::new(&base_url, "secret");
+
+        assert_eq!(
+            client.get_puzzle(puzzle!(2024, 6)).unwrap(),
+            "## --- Day 6 ---\n\nSome *text* & `code`.\n\n```\n#..\n.^.\n```"
+        );
+    }
+
+    #[test]
+    fn handles_unauthorized() {
+        let (base_url, _rx) = serve_once(400, "Puzzle inputs differ by user. Please log in.");
+        let client = AocClient::new(&base_url, "expired");
+        assert!(matches!(
+            client.get_input(puzzle!(2024, 6)),
+            Err(AocClientError::Unauthorized)
+        ));
+    }
+
+    #[test]
+    fn handles_locked_puzzles() {
+        let (base_url, _rx) = serve_once(404, "Please don't repeatedly request this endpoint.");
+        let client = AocClient::new(&base_url, "secret");
+        assert!(matches!(
+            client.get_input(puzzle!(2024, 25)),
+            Err(AocClientError::NotYetUnlocked)
+        ));
+    }
+
+    #[test]
+    fn submits_correct_answer() {
+        let (base_url, rx) = serve_once(
+            200,
+            "<main><article><p>That's the right answer! You are one gold star closer.</p></article></main>",
+        );
+        let client = AocClient::new(&base_url, "secret");
+
+        assert_eq!(
+            client.submit(puzzle!(2024, 6), 2, "41").unwrap(),
+            Outcome::Correct
+        );
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("POST /2024/day/6/answer "));
+        assert!(request.ends_with("level=2&answer=41"));
+    }
+
+    #[test]
+    fn handles_wrong_answer() {
+        let (base_url, _rx) = serve_once(
+            200,
+            "<main><article><p>That's not the right answer; your answer is too high.</p></article></main>",
+        );
+        let client = AocClient::new(&base_url, "secret");
+        assert_eq!(
+            client.submit(puzzle!(2024, 6), 1, "42").unwrap(),
+            Outcome::TooHigh
+        );
+    }
+
+    #[test]
+    fn handles_rate_limit() {
+        let (base_url, _rx) = serve_once(
+            200,
+            "<main><article><p>You gave an answer too recently. You have 39s left to wait.</p></article></main>",
+        );
+        let client = AocClient::new(&base_url, "secret");
+        assert_eq!(
+            client.submit(puzzle!(2024, 6), 1, "42").unwrap(),
+            Outcome::RateLimited(Some(Duration::from_secs(39)))
+        );
+    }
+
+    #[test]
+    fn parses_submission_outcomes() {
+        assert_eq!(
+            Outcome::from_message("That's not the right answer. If you're stuck, ..."),
+            Outcome::Incorrect
+        );
+        assert_eq!(
+            Outcome::from_message("That's not the right answer; your answer is too low."),
+            Outcome::TooLow
+        );
+        assert_eq!(
+            Outcome::from_message(
+                "You don't seem to be solving the right level. Did you already complete it?"
+            ),
+            Outcome::AlreadySolved
+        );
+        assert_eq!(
+            Outcome::from_message("You gave an answer too recently. You have 1m 5s left to wait."),
+            Outcome::RateLimited(Some(Duration::from_secs(65)))
+        );
+        assert_eq!(
+            Outcome::from_message("You gave an answer too recently."),
+            Outcome::RateLimited(None)
+        );
+    }
+
+    #[test]
+    fn extracts_articles() {
+        let html = "<article class=\"day-desc\">one</article><p>x</p><article>two</article>";
+        assert_eq!(articles(html), vec!["one", "two"]);
+        assert_eq!(
+            html_to_markdown("<ul><li>a</li><li>b</li></ul>"),
+            "- a\n- b"
+        );
+    }
+}
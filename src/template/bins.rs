@@ -0,0 +1,341 @@
+/// Builds the solution binaries of several days with a single `cargo build` and runs them directly,
+/// instead of invoking `cargo run` for every day.
+use std::{
+    collections::HashMap,
+    env, io,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::output::parse_record;
+use crate::template::runner::PartResult;
+use crate::template::PuzzleId;
+
+/// The solution binaries of a build, see [`build`].
+#[derive(Debug, Default)]
+pub struct Bins {
+    executables: HashMap<PuzzleId, PathBuf>,
+    /// Compiler errors of the solutions that failed to compile.
+    errors: HashMap<PuzzleId, String>,
+}
+
+/// The binary of a single solution.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Bin<'a> {
+    /// The solution has not been scaffolded.
+    Missing,
+    /// The solution failed to compile, with the compiler errors.
+    Failed(&'a str),
+    Compiled(&'a Path),
+}
+
+impl Bins {
+    pub fn get(&self, puzzle: PuzzleId) -> Bin<'_> {
+        if let Some(executable) = self.executables.get(&puzzle) {
+            Bin::Compiled(executable)
+        } else if let Some(errors) = self.errors.get(&puzzle) {
+            Bin::Failed(errors)
+        } else {
+            Bin::Missing
+        }
+    }
+}
+
+/// The cargo profile and features that solution binaries are built with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BuildOptions {
+    pub profile: &'static str,
+    pub features: Vec<&'static str>,
+}
+
+impl BuildOptions {
+    /// The profile and features of the running binary, e.g. `dhat-heap` for `cargo run --features dhat-heap -- time`.
+    pub fn current() -> Self {
+        let profile = if cfg!(feature = "dhat-heap") {
+            "dhat"
+        } else if cfg!(debug_assertions) {
+            "dev"
+        } else {
+            "release"
+        };
+
+        let mut features = vec![];
+        if cfg!(feature = "dhat-heap") {
+            features.push("dhat-heap");
+        }
+        if cfg!(feature = "visualize") {
+            features.push("visualize");
+        }
+
+        Self { profile, features }
+    }
+}
+
+/// The source file of a solution.
+pub fn source_path(puzzle: PuzzleId) -> PathBuf {
+    PathBuf::from(format!("src/bin/{puzzle}.rs"))
+}
+
+/// Build the binaries of all given solutions that exist.
+///
+/// Solutions that fail to compile do not stop the build of the others, their errors are returned instead.
+pub fn build(puzzles: &[PuzzleId], options: &BuildOptions) -> io::Result<Bins> {
+    let puzzles: Vec<PuzzleId> = puzzles
+        .iter()
+        .copied()
+        .filter(|puzzle| source_path(*puzzle).exists())
+        .collect();
+
+    if puzzles.is_empty() {
+        return Ok(Bins::default());
+    }
+
+    let mut command = Command::new("cargo");
+    // NOTE: cargo reports its progress on stderr, pass it through so that long builds do not look stuck.
+    command
+        .args(build_args(&puzzles, options))
+        .stdin(Stdio::null())
+        .stderr(Stdio::inherit());
+
+    // NOTE: `cargo run` passes the package's environment to the running binary. Some build scripts of dependencies
+    // watch these variables and would be rerun, and everything depending on them rebuilt, if they were passed on.
+    for (key, _) in env::vars() {
+        let is_package_var = key.starts_with("CARGO_PKG_")
+            || matches!(
+                key.as_str(),
+                "CARGO_MANIFEST_DIR"
+                    | "CARGO_MANIFEST_PATH"
+                    | "CARGO_CRATE_NAME"
+                    | "CARGO_BIN_NAME"
+                    | "CARGO_PRIMARY_PACKAGE"
+            );
+        if is_package_var {
+            command.env_remove(key);
+        }
+    }
+
+    let output = command.output()?;
+
+    let Messages {
+        executables,
+        mut errors,
+        warnings,
+    } = parse_messages(&String::from_utf8_lossy(&output.stdout));
+
+    // NOTE: errors are reported with the day that failed to compile, warnings would be lost otherwise.
+    eprint!("{warnings}");
+
+    let mut bins = Bins::default();
+    // NOTE: errors that do not belong to a solution, e.g. of the library, fail every solution.
+    // Without any, cargo itself failed and already printed why.
+    let shared_errors = errors
+        .remove("")
+        .unwrap_or_else(|| format!("`cargo build` failed with {}.", output.status));
+
+    for puzzle in puzzles {
+        let name = puzzle.to_string();
+        if let Some(executable) = executables.get(&name) {
+            bins.executables.insert(puzzle, executable.clone());
+        } else {
+            let errors = errors
+                .remove(&name)
+                .unwrap_or_else(|| shared_errors.clone());
+            bins.errors.insert(puzzle, errors);
+        }
+    }
+
+    Ok(bins)
+}
+
+/// The arguments of `cargo` to build the binaries of the given solutions.
+fn build_args(puzzles: &[PuzzleId], options: &BuildOptions) -> Vec<String> {
+    let mut args = vec![
+        "build".to_string(),
+        "--keep-going".to_string(),
+        "--message-format".to_string(),
+        "json-diagnostic-rendered-ansi".to_string(),
+        "--profile".to_string(),
+        options.profile.to_string(),
+    ];
+    if !options.features.is_empty() {
+        args.extend(["--features".to_string(), options.features.join(",")]);
+    }
+    for puzzle in puzzles {
+        args.extend(["--bin".to_string(), puzzle.to_string()]);
+    }
+    args
+}
+
+/// The relevant JSON messages of `cargo build`.
+#[derive(Debug, Default)]
+struct Messages {
+    /// The executables by the name of their binary.
+    executables: HashMap<String, PathBuf>,
+    /// The rendered compiler errors by the name of the binary, under an empty name for targets other than binaries.
+    errors: HashMap<String, String>,
+    /// All other rendered compiler diagnostics, e.g. warnings.
+    warnings: String,
+}
+
+/// Collect the executables and the compiler diagnostics from the JSON messages of `cargo build`.
+fn parse_messages(stdout: &str) -> Messages {
+    let mut messages = Messages::default();
+
+    for line in stdout.lines() {
+        let Ok(JsonValue::Object(message)) = line.parse::<JsonValue>() else {
+            continue;
+        };
+        let string = |value: Option<&JsonValue>| value.and_then(|v| v.get::<String>()).cloned();
+        let target = message
+            .get("target")
+            .and_then(|t| t.get::<HashMap<String, JsonValue>>());
+        let is_bin = target
+            .and_then(|t| t.get("kind"))
+            .and_then(|kind| kind.get::<Vec<JsonValue>>())
+            .is_some_and(|kind| kind.contains(&JsonValue::String("bin".into())));
+        let name = if is_bin {
+            string(target.and_then(|t| t.get("name"))).unwrap_or_default()
+        } else {
+            String::new()
+        };
+
+        match string(message.get("reason")).as_deref() {
+            Some("compiler-artifact") if is_bin => {
+                if let Some(executable) = string(message.get("executable")) {
+                    messages.executables.insert(name, executable.into());
+                }
+            }
+            Some("compiler-message") => {
+                let diagnostic = message
+                    .get("message")
+                    .and_then(|m| m.get::<HashMap<String, JsonValue>>());
+                let level = string(diagnostic.and_then(|d| d.get("level")));
+                let rendered = string(diagnostic.and_then(|d| d.get("rendered")));
+                match (level.as_deref(), rendered) {
+                    (Some("error"), Some(rendered)) => {
+                        messages.errors.entry(name).or_default().push_str(&rendered);
+                    }
+                    (_, Some(rendered)) => messages.warnings.push_str(&rendered),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    messages
+}
+
+/// Run a solution binary and read back the results of its steps.
+pub fn run(executable: &Path, is_timed: bool) -> Result<Vec<PartResult>, String> {
+    let mut command = Command::new(executable);
+    command.args(["--format", "ndjson"]);
+    if is_timed {
+        command.arg("--time");
+    }
+
+    let output = command
+        .stdin(Stdio::null())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| format!("failed to run {}: {e}", executable.display()))?;
+
+    if !output.status.success() {
+        return Err(format!("the solution exited with {}", output.status));
+    }
+
+    let mut results = vec![];
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        // NOTE: solutions may print output of their own, e.g. for debugging. Pass it on,
+        // but keep stdout parseable for machine-readable formats.
+        if line.starts_with('{') {
+            results.push(parse_record(line)?);
+        } else {
+            eprintln!("{line}");
+        }
+    }
+    Ok(results)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{build_args, parse_messages, BuildOptions};
+    use crate::puzzle;
+
+    #[test]
+    fn generates_build_args() {
+        let options = BuildOptions {
+            profile: "dhat",
+            features: vec!["dhat-heap", "visualize"],
+        };
+        assert_eq!(
+            build_args(&[puzzle!(2024, 6), puzzle!(2024, 7)], &options),
+            [
+                "build",
+                "--keep-going",
+                "--message-format",
+                "json-diagnostic-rendered-ansi",
+                "--profile",
+                "dhat",
+                "--features",
+                "dhat-heap,visualize",
+                "--bin",
+                "2024-06",
+                "--bin",
+                "2024-07",
+            ]
+        );
+
+        let options = BuildOptions {
+            profile: "release",
+            features: vec![],
+        };
+        assert_eq!(
+            build_args(&[puzzle!(2024, 6)], &options),
+            [
+                "build",
+                "--keep-going",
+                "--message-format",
+                "json-diagnostic-rendered-ansi",
+                "--profile",
+                "release",
+                "--bin",
+                "2024-06",
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_build_messages() {
+        let stdout = [
+            r#"{"reason":"compiler-artifact","target":{"kind":["lib"],"name":"advent_of_code"},"executable":null}"#,
+            r#"{"reason":"compiler-artifact","target":{"kind":["bin"],"name":"2024-06"},"executable":"/target/release/2024-06"}"#,
+            r#"{"reason":"compiler-message","target":{"kind":["bin"],"name":"2024-07"},"message":{"level":"warning","rendered":"warning: unused\n"}}"#,
+            r#"{"reason":"compiler-message","target":{"kind":["bin"],"name":"2024-07"},"message":{"level":"error","rendered":"error[E0308]: mismatched types\n"}}"#,
+            r#"{"reason":"compiler-message","target":{"kind":["lib"],"name":"advent_of_code"},"message":{"level":"error","rendered":"error: expected `;`\n"}}"#,
+            r#"{"reason":"build-finished","success":false}"#,
+            "not json",
+        ]
+        .join("\n");
+
+        let messages = parse_messages(&stdout);
+        assert_eq!(messages.executables.len(), 1);
+        assert_eq!(
+            messages.executables["2024-06"],
+            PathBuf::from("/target/release/2024-06")
+        );
+        assert_eq!(messages.errors.len(), 2);
+        assert_eq!(
+            messages.errors["2024-07"],
+            "error[E0308]: mismatched types\n"
+        );
+        assert_eq!(messages.errors[""], "error: expected `;`\n");
+        assert_eq!(messages.warnings, "warning: unused\n");
+    }
+}
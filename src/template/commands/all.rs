@@ -1,5 +1,13 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::output::OutputFormat;
+use crate::template::{all_days, run_multi::run_multi, Year};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+/// Run all days of a year, one after another unless `jobs` is given.
+pub fn handle(year: Year, format: OutputFormat, jobs: Option<usize>) {
+    run_multi(
+        year,
+        &all_days(year).collect(),
+        false,
+        format,
+        jobs.unwrap_or(1),
+    );
 }
@@ -1,34 +1,112 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::bins::{self, Bin, BuildOptions};
+use crate::template::output::OutputFormat;
+use crate::template::visualize::RenderFormat;
+use crate::template::PuzzleId;
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// How the `solve` command builds and runs a solution.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SolveOptions {
+    pub release: bool,
+    pub dhat: bool,
+    pub submit_part: Option<u8>,
+    pub format: OutputFormat,
+    /// Show the debug output of the solution.
+    pub verbose: bool,
+    /// Replay the frames that the solution records in the terminal.
+    pub visualize: bool,
+    /// Render the frames that the solution records to files.
+    pub render: Option<RenderFormat>,
+}
 
-    if dhat {
-        cmd_args.extend([
-            "--profile".to_string(),
-            "dhat".to_string(),
-            "--features".to_string(),
-            "dhat-heap".to_string(),
-        ]);
+pub fn handle(puzzle: PuzzleId, options: SolveOptions) {
+    let SolveOptions {
+        release,
+        dhat,
+        submit_part,
+        format,
+        verbose,
+        visualize,
+        render,
+    } = options;
+
+    let profile = if dhat {
+        "dhat"
     } else if release {
-        cmd_args.push("--release".to_string());
+        "release"
+    } else {
+        "dev"
+    };
+
+    let mut features = vec![];
+    if dhat {
+        features.push("dhat-heap");
     }
+    if visualize || render.is_some() {
+        features.push("visualize");
+    }
+
+    let build_options = BuildOptions { profile, features };
+    let bins = match bins::build(&[puzzle], &build_options) {
+        Ok(bins) => bins,
+        Err(e) => {
+            eprintln!("Failed to build solution: {e}");
+            process::exit(1);
+        }
+    };
 
-    cmd_args.push("--".to_string());
+    let executable = match bins.get(puzzle) {
+        Bin::Compiled(executable) => executable,
+        Bin::Failed(errors) => {
+            eprintln!("{}", errors.trim_end());
+            eprintln!("Solution {puzzle} failed to compile.");
+            process::exit(1);
+        }
+        Bin::Missing => {
+            eprintln!(
+                "No solution for {puzzle}, expected {}.",
+                bins::source_path(puzzle).display()
+            );
+            process::exit(1);
+        }
+    };
+
+    let mut cmd_args = vec![];
+
+    match format {
+        OutputFormat::Text => {}
+        OutputFormat::Json => cmd_args.extend(["--format".to_string(), "json".to_string()]),
+        OutputFormat::Ndjson => cmd_args.extend(["--format".to_string(), "ndjson".to_string()]),
+    }
+
+    if verbose {
+        cmd_args.push("--verbose".to_string());
+    }
+
+    if visualize {
+        cmd_args.push("--visualize".to_string());
+    }
+
+    if let Some(render) = render {
+        cmd_args.push("--render".to_string());
+        cmd_args.push(render.to_string());
+    }
 
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
+    let status = Command::new(executable)
         .args(&cmd_args)
+        .stdin(Stdio::inherit())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
-        .spawn()
+        .status()
         .unwrap();
 
-    cmd.wait().unwrap();
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
@@ -1,19 +1,37 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, process};
 
+use crate::template::config::config;
+use crate::template::output::OutputFormat;
 use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::runner::Step;
+use crate::template::timings::{Delta, Timings};
+use crate::template::{all_days, readme_benchmarks, Day, Year, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    compare: bool,
+    format: OutputFormat,
+    jobs: Option<usize>,
+) {
+    if cfg!(debug_assertions) {
+        eprintln!(
+            "Warning: benching an unoptimized build. Use `cargo time` to bench a release build."
+        );
+    }
+
+    let stored_timings = Timings::read_from_file(year);
 
     let days_to_run = day.map_or_else(
         || {
-            if run_all {
-                all_days().collect()
+            // NOTE: comparing needs fresh timings of the days that are already benched.
+            if run_all || compare {
+                all_days(year).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
+                all_days(year)
                     .filter(|day| !stored_timings.is_day_complete(*day))
                     .collect()
             }
@@ -21,20 +39,89 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    // NOTE: days are benched one after another unless asked otherwise, so that they do not disturb each other.
+    let jobs = jobs.unwrap_or(1);
+    if jobs > 1 {
+        eprintln!("Warning: benching {jobs} days at once. Timings are affected by the other days.");
+    }
+
+    let timings = run_multi(year, &days_to_run, true, format, jobs).unwrap();
+
+    let regressions = if compare {
+        let deltas = stored_timings.compare(&timings, &days_to_run);
+        print_comparison(&deltas, config().regression_threshold, format)
+    } else {
+        0
+    };
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year).unwrap();
 
-        println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
+        match readme_benchmarks::update(year, merged_timings) {
+            // NOTE: keep stdout parseable for machine-readable formats.
+            Ok(()) if format == OutputFormat::Text => {
+                println!();
                 println!("Stored updated benchmarks.");
             }
+            Ok(()) => {
+                eprintln!("Stored updated benchmarks.");
+            }
             Err(_) => {
                 eprintln!("Failed to store updated benchmarks.");
             }
         }
     }
+
+    if regressions > 0 {
+        process::exit(1);
+    }
+}
+
+/// Print the change of each step compared to the stored timings and return the number of regressions.
+fn print_comparison(deltas: &[Delta], threshold: u64, format: OutputFormat) -> usize {
+    let mut lines = vec![];
+
+    for delta in deltas {
+        let step = match delta.step {
+            Step::Parse => "Parse".to_string(),
+            Step::Part(part) => format!("Part {part}"),
+        };
+        let mut line = match (delta.fresh, delta.percent()) {
+            (Some(fresh), Some(percent)) => format!(
+                "Day {} {step}: {:.1?} → {fresh:.1?} ({percent:+.1}%)",
+                delta.day, delta.stored
+            ),
+            _ => format!("Day {} {step}: {:.1?} → not timed", delta.day, delta.stored),
+        };
+        if delta.is_regression(threshold) {
+            line.push_str(" ✖ regression");
+        }
+        lines.push(line);
+    }
+
+    let regressions = deltas.iter().filter(|d| d.is_regression(threshold)).count();
+    let summary = if deltas.is_empty() {
+        "No stored timings to compare with.".to_string()
+    } else if regressions == 0 {
+        format!("No step got slower by more than {threshold}%.")
+    } else {
+        format!(
+            "{regressions} step(s) got slower by more than {threshold}% or are no longer timed."
+        )
+    };
+
+    // NOTE: keep stdout parseable for machine-readable formats.
+    if format == OutputFormat::Text {
+        println!();
+        println!("{ANSI_BOLD}Compared to stored timings{ANSI_RESET}");
+        println!("------");
+        lines.iter().for_each(|line| println!("{line}"));
+        println!("{summary}");
+    } else {
+        lines.iter().for_each(|line| eprintln!("{line}"));
+        eprintln!("{summary}");
+    }
+
+    regressions
 }
@@ -0,0 +1,151 @@
+use std::{collections::HashSet, process};
+
+use crate::template::answers::Answers;
+use crate::template::run_multi::{build, run_day, DayOutcome};
+use crate::template::runner::Step;
+use crate::template::{all_days, Day, PuzzleId, Year, ANSI_BOLD, ANSI_RESET};
+
+/// Run solutions against their real inputs and compare the answers to the ones stored in `data/<year>/answers.json`.
+/// Exits with a non-zero status if any answer does not match or any step of a solution fails.
+/// A day that is requested explicitly also fails if it has no solution, no input or no stored answers.
+pub fn handle(year: Year, day: Option<Day>, run_all: bool) {
+    let answers = match Answers::read_from_file(year) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Failed to read stored answers: {e}");
+            process::exit(1);
+        }
+    };
+    let is_explicit = day.is_some();
+
+    let days_to_run: HashSet<Day> = day.map_or_else(
+        || {
+            if run_all {
+                all_days(year).collect()
+            } else {
+                // when the `--all` flag is not set, only verify days that have stored answers.
+                all_days(year)
+                    .filter(|day| answers.get(*day).is_some())
+                    .collect()
+            }
+        },
+        |day| HashSet::from([day]),
+    );
+
+    let days: Vec<Day> = all_days(year)
+        .filter(|day| days_to_run.contains(day))
+        .collect();
+    let bins = build(year, &days);
+
+    let mut mismatches = 0;
+    let mut failures = 0;
+    let mut unverified = 0;
+    let mut need_space = false;
+
+    for day in days {
+        let outcome = run_day(&bins, PuzzleId::new(year, day), false);
+
+        if matches!(outcome, DayOutcome::NotSolved) && !is_explicit {
+            continue;
+        }
+
+        if need_space {
+            println!();
+        }
+        need_space = true;
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let results = match outcome {
+            DayOutcome::Ran(results) => results,
+            DayOutcome::NotSolved => {
+                unverified += 1;
+                println!("Not solved.");
+                continue;
+            }
+            DayOutcome::NoInput => {
+                if is_explicit {
+                    unverified += 1;
+                }
+                println!("No input file.");
+                continue;
+            }
+            DayOutcome::CompileError(errors) => {
+                failures += 1;
+                println!("✖ Failed to compile.");
+                println!("{}", errors.trim_end());
+                continue;
+            }
+            DayOutcome::Failed(error) => {
+                failures += 1;
+                println!("✖ {error}");
+                continue;
+            }
+        };
+
+        let expected = answers.get(day);
+        if expected.is_none() && is_explicit {
+            unverified += 1;
+            println!("No stored answers.");
+        }
+
+        for result in &results {
+            // NOTE: a step that failed, e.g. because it panicked or timed out, fails verification on its own,
+            // even if the step is the parser or there is no stored answer to compare against.
+            if let Some(error) = &result.error {
+                failures += 1;
+                match result.step {
+                    Step::Parse => println!("Parse: ✖ {error}"),
+                    Step::Part(part) => println!("Part {part}: ✖ {error}"),
+                }
+                continue;
+            }
+
+            let Step::Part(part) = result.step else {
+                continue;
+            };
+
+            let expected = expected.and_then(|a| a.part(part));
+            let actual = result.answer.as_deref();
+
+            match (expected, actual) {
+                (None, _) => println!("Part {part}: no stored answer"),
+                (Some(expected), Some(actual)) if expected == actual => {
+                    println!("Part {part}: ✔ {actual}");
+                }
+                (Some(expected), actual) => {
+                    mismatches += 1;
+                    let actual = actual.unwrap_or("no answer");
+                    println!("Part {part}: ✖ expected {expected}, got {actual}");
+                }
+            }
+        }
+
+        for part in [1, 2] {
+            let Some(expected) = expected.and_then(|a| a.part(part)) else {
+                continue;
+            };
+            if !results.iter().any(|result| result.step == Step::Part(part)) {
+                mismatches += 1;
+                println!("Part {part}: ✖ expected {expected}, got no result");
+            }
+        }
+    }
+
+    if failures > 0 {
+        eprintln!("\n{failures} solution(s) or step(s) failed to run.");
+    }
+
+    if mismatches > 0 {
+        eprintln!("\n{mismatches} answer(s) did not match.");
+    }
+
+    if unverified > 0 {
+        eprintln!("\n{unverified} day(s) could not be verified.");
+    }
+
+    if failures > 0 || mismatches > 0 || unverified > 0 {
+        process::exit(1);
+    }
+}
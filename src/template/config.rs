@@ -0,0 +1,403 @@
+/// Project configuration, read from `aoc.toml` in the working directory.
+/// Every setting can be overridden with an environment variable.
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+    sync::OnceLock,
+    time::Duration,
+};
+
+use toml::{Table, Value};
+
+use crate::template::{Year, MAX_DAY};
+
+static CONFIG_FILE_PATH: &str = "aoc.toml";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// The default year of all commands.
+    pub year: Option<Year>,
+    /// Directory that contains a sub-directory with inputs, examples and puzzles for each year.
+    pub data_dir: PathBuf,
+    /// Template for new solutions.
+    pub template: PathBuf,
+    /// Readme that contains the benchmark tables.
+    pub readme: PathBuf,
+    /// Label of the markers around benchmark tables in the readme.
+    pub benchmark_marker: String,
+    /// Approximate time spent benching each part.
+    pub bench_budget: Duration,
+    /// Wall-clock time after which a part is reported as timed out, `None` to wait forever.
+    pub part_timeout: Option<Duration>,
+    /// Percentage by which a step may get slower before `time --compare` reports a regression.
+    pub regression_threshold: u64,
+    /// File that contains the session cookie.
+    pub session_file: Option<PathBuf>,
+    /// Base URL of the advent of code website.
+    pub base_url: String,
+    /// Number of puzzles of years whose calendar differs from the default.
+    pub calendar: HashMap<u16, u8>,
+    /// Colors of the cells in rendered visualizations, by the character that is drawn in a cell.
+    pub palette: HashMap<char, [u8; 3]>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: None,
+            data_dir: "data".into(),
+            template: "src/template.txt".into(),
+            readme: "README.md".into(),
+            benchmark_marker: "benchmarking table".into(),
+            bench_budget: Duration::from_secs(1),
+            part_timeout: Some(Duration::from_secs(60)),
+            regression_threshold: 10,
+            session_file: home_dir().map(|home| home.join(".adventofcode.session")),
+            base_url: "https://adventofcode.com".into(),
+            calendar: HashMap::new(),
+            palette: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// The configuration of the project, loaded on first use.
+/// Exits the process if the configuration is invalid.
+pub fn config() -> &'static Config {
+    static CONFIG: OnceLock<Config> = OnceLock::new();
+
+    CONFIG.get_or_init(|| match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to load {CONFIG_FILE_PATH}: {e}");
+            process::exit(1);
+        }
+    })
+}
+
+impl Config {
+    /// Read the configuration file and apply overrides from the environment.
+    /// If the file is not present, the defaults are used.
+    pub fn load() -> Result<Self, Error> {
+        let path = env::var("AOC_CONFIG").unwrap_or_else(|_| CONFIG_FILE_PATH.into());
+
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        Self::parse(&contents, |key| env::var(key).ok())
+    }
+
+    /// Parse a configuration file, using `var` to look up environment overrides.
+    fn parse(contents: &str, var: impl Fn(&str) -> Option<String>) -> Result<Self, Error> {
+        let table: Table = contents
+            .parse()
+            .map_err(|e: toml::de::Error| Error::Parser(e.message().into()))?;
+
+        let mut config = Config::default();
+
+        for (key, value) in &table {
+            match key.as_str() {
+                "year" => config.year = Some(parse_year(value, key)?),
+                "data_dir" => config.data_dir = as_str(value, key)?.into(),
+                "template" => config.template = as_str(value, key)?.into(),
+                "readme" => config.readme = as_str(value, key)?.into(),
+                "benchmark_marker" => config.benchmark_marker = as_str(value, key)?.into(),
+                "bench_budget_ms" => {
+                    config.bench_budget = Duration::from_millis(as_u64(value, key)?);
+                }
+                "part_timeout_secs" => {
+                    config.part_timeout = parse_timeout(as_u64(value, key)?);
+                }
+                "regression_threshold_pct" => config.regression_threshold = as_u64(value, key)?,
+                "session_file" => config.session_file = Some(expand_home(as_str(value, key)?)),
+                "base_url" => config.base_url = as_str(value, key)?.into(),
+                "calendar" => {
+                    let calendar = value
+                        .as_table()
+                        .ok_or_else(|| expected(key, "a table of years"))?;
+                    for (year, days) in calendar {
+                        let year = year
+                            .parse::<Year>()
+                            .map_err(|_| expected("calendar", "keys to be years"))?;
+                        config
+                            .calendar
+                            .insert(year.into_inner(), parse_days(days, "calendar")?);
+                    }
+                }
+                "palette" => {
+                    let palette = value
+                        .as_table()
+                        .ok_or_else(|| expected(key, "a table of characters"))?;
+                    for (cell, color) in palette {
+                        let mut chars = cell.chars();
+                        let (Some(cell), None) = (chars.next(), chars.next()) else {
+                            return Err(expected("palette", "keys to be single characters"));
+                        };
+                        config.palette.insert(cell, parse_color(color, "palette")?);
+                    }
+                }
+                _ => return Err(Error::Parser(format!("unknown setting `{key}`."))),
+            }
+        }
+
+        config.apply_overrides(var)?;
+        Ok(config)
+    }
+
+    fn apply_overrides(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<(), Error> {
+        if let Some(year) = var("AOC_YEAR") {
+            self.year = Some(
+                year.parse()
+                    .map_err(|_| expected("AOC_YEAR", "a year of 2015 or later"))?,
+            );
+        }
+        if let Some(data_dir) = var("AOC_DATA_DIR") {
+            self.data_dir = data_dir.into();
+        }
+        if let Some(template) = var("AOC_TEMPLATE") {
+            self.template = template.into();
+        }
+        if let Some(readme) = var("AOC_README") {
+            self.readme = readme.into();
+        }
+        if let Some(marker) = var("AOC_BENCHMARK_MARKER") {
+            self.benchmark_marker = marker;
+        }
+        if let Some(budget) = var("AOC_BENCH_BUDGET_MS") {
+            self.bench_budget = Duration::from_millis(
+                budget
+                    .parse()
+                    .map_err(|_| expected("AOC_BENCH_BUDGET_MS", "a number"))?,
+            );
+        }
+        if let Some(timeout) = var("AOC_PART_TIMEOUT_SECS") {
+            self.part_timeout = parse_timeout(
+                timeout
+                    .parse()
+                    .map_err(|_| expected("AOC_PART_TIMEOUT_SECS", "a number"))?,
+            );
+        }
+        if let Some(threshold) = var("AOC_REGRESSION_THRESHOLD_PCT") {
+            self.regression_threshold = threshold
+                .parse()
+                .map_err(|_| expected("AOC_REGRESSION_THRESHOLD_PCT", "a number"))?;
+        }
+        if let Some(session_file) = var("AOC_SESSION_FILE") {
+            self.session_file = Some(expand_home(&session_file));
+        }
+        if let Some(base_url) = var("AOC_BASE_URL") {
+            self.base_url = base_url;
+        }
+        // NOTE: the calendar is overridden per year, so every year is looked up.
+        for year in Year::all() {
+            let key = format!("AOC_DAYS_{year}");
+            if let Some(days) = var(&key) {
+                let days = days
+                    .parse()
+                    .ok()
+                    .filter(|days| (1..=MAX_DAY).contains(days))
+                    .ok_or_else(|| expected(&key, "a number of days between 1 and 99"))?;
+                self.calendar.insert(year.into_inner(), days);
+            }
+        }
+        Ok(())
+    }
+
+    /// The number of puzzles in the calendar of a year, if configured.
+    /// Can be overridden with an environment variable like `AOC_DAYS_2025`.
+    pub fn days(&self, year: Year) -> Option<u8> {
+        self.calendar.get(&year.into_inner()).copied()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn expected(key: &str, what: &str) -> Error {
+    Error::Parser(format!("expected `{key}` to be {what}."))
+}
+
+fn as_str<'a>(value: &'a Value, key: &str) -> Result<&'a str, Error> {
+    value.as_str().ok_or_else(|| expected(key, "a string"))
+}
+
+fn as_u64(value: &Value, key: &str) -> Result<u64, Error> {
+    value
+        .as_integer()
+        .and_then(|x| u64::try_from(x).ok())
+        .ok_or_else(|| expected(key, "a positive number"))
+}
+
+fn parse_year(value: &Value, key: &str) -> Result<Year, Error> {
+    as_u64(value, key)
+        .ok()
+        .and_then(|x| u16::try_from(x).ok())
+        .and_then(Year::new)
+        .ok_or_else(|| expected(key, "a year of 2015 or later"))
+}
+
+fn parse_days(value: &Value, key: &str) -> Result<u8, Error> {
+    as_u64(value, key)
+        .ok()
+        .and_then(|x| u8::try_from(x).ok())
+        .filter(|x| (1..=MAX_DAY).contains(x))
+        .ok_or_else(|| expected(key, "a number of days between 1 and 99"))
+}
+
+/// A timeout of zero seconds disables the timeout.
+fn parse_timeout(secs: u64) -> Option<Duration> {
+    (secs > 0).then(|| Duration::from_secs(secs))
+}
+
+/// Parse a color like `#ff8800`.
+fn parse_color(value: &Value, key: &str) -> Result<[u8; 3], Error> {
+    let hex = as_str(value, key)?
+        .strip_prefix('#')
+        .filter(|hex| hex.len() == 6 && hex.is_ascii())
+        .ok_or_else(|| expected(key, "colors like `#ff8800`"))?;
+
+    let mut color = [0; 3];
+    for (i, channel) in color.iter_mut().enumerate() {
+        *channel = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)
+            .map_err(|_| expected(key, "colors like `#ff8800`"))?;
+    }
+    Ok(color)
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+/// Expand a leading `~` to the home directory.
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => Path::new(path).into(),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{collections::HashMap, path::PathBuf, time::Duration};
+
+    use super::Config;
+    use crate::year;
+
+    fn no_env(_: &str) -> Option<String> {
+        None
+    }
+
+    #[test]
+    fn uses_defaults_for_empty_file() {
+        assert_eq!(Config::parse("", no_env).unwrap(), Config::default());
+    }
+
+    #[test]
+    fn parses_settings() {
+        let config = Config::parse(
+            r##"
+            year = 2023
+            data_dir = "puzzles"
+            template = "template.rs"
+            readme = "docs/README.md"
+            benchmark_marker = "timings"
+            bench_budget_ms = 250
+            part_timeout_secs = 10
+            regression_threshold_pct = 5
+            session_file = "/tmp/session"
+            base_url = "http://localhost:8080"
+
+            [calendar]
+            2025 = 12
+
+            [palette]
+            "#" = "#FFFFFF"
+            "." = "#0f0f23"
+            "##,
+            no_env,
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Some(year!(2023)));
+        assert_eq!(config.data_dir, PathBuf::from("puzzles"));
+        assert_eq!(config.template, PathBuf::from("template.rs"));
+        assert_eq!(config.readme, PathBuf::from("docs/README.md"));
+        assert_eq!(config.benchmark_marker, "timings");
+        assert_eq!(config.bench_budget, Duration::from_millis(250));
+        assert_eq!(config.part_timeout, Some(Duration::from_secs(10)));
+        assert_eq!(config.regression_threshold, 5);
+        assert_eq!(config.session_file, Some(PathBuf::from("/tmp/session")));
+        assert_eq!(config.base_url, "http://localhost:8080");
+        assert_eq!(config.calendar, HashMap::from([(2025, 12)]));
+        assert_eq!(
+            config.palette,
+            HashMap::from([('#', [255, 255, 255]), ('.', [15, 15, 35])])
+        );
+    }
+
+    #[test]
+    fn applies_env_overrides() {
+        let config = Config::parse("year = 2023\ndata_dir = \"puzzles\"", |key| match key {
+            "AOC_YEAR" => Some("2024".into()),
+            "AOC_BENCH_BUDGET_MS" => Some("100".into()),
+            "AOC_PART_TIMEOUT_SECS" => Some("0".into()),
+            "AOC_REGRESSION_THRESHOLD_PCT" => Some("25".into()),
+            "AOC_DAYS_2025" => Some("10".into()),
+            _ => None,
+        })
+        .unwrap();
+
+        assert_eq!(config.year, Some(year!(2024)));
+        assert_eq!(config.data_dir, PathBuf::from("puzzles"));
+        assert_eq!(config.bench_budget, Duration::from_millis(100));
+        assert_eq!(config.part_timeout, None);
+        assert_eq!(config.regression_threshold, 25);
+        assert_eq!(config.days(year!(2025)), Some(10));
+        assert_eq!(config.days(year!(2024)), None);
+    }
+
+    #[test]
+    fn errors_for_invalid_settings() {
+        assert!(Config::parse("year = 2014", no_env).is_err());
+        assert!(Config::parse("year = \"2024\"", no_env).is_err());
+        assert!(Config::parse("readme = 1", no_env).is_err());
+        assert!(Config::parse("regression_threshold_pct = -5", no_env).is_err());
+        assert!(Config::parse("[calendar]\n2025 = 100", no_env).is_err());
+        assert!(Config::parse("[palette]\n\"##\" = \"#ffffff\"", no_env).is_err());
+        assert!(Config::parse("[palette]\n\"#\" = \"white\"", no_env).is_err());
+        assert!(Config::parse("[palette]\n\"#\" = \"#fffffg\"", no_env).is_err());
+        assert!(Config::parse("unknown = 1", no_env).is_err());
+        assert!(Config::parse("year = ", no_env).is_err());
+        assert!(Config::parse("", |key| (key == "AOC_DAYS_2025").then(|| "100".into())).is_err());
+    }
+}
@@ -0,0 +1,69 @@
+/// Debug output of solutions that is only shown when a solution is run with `--verbose`.
+///
+/// Use the [`debug!`](crate::debug) macro instead of `println!` in solutions. Its arguments are only
+/// formatted when debug output is enabled, and it writes to stderr so that it never mixes with the results.
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Checks whether debug output is enabled.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Enables or disables debug output.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Disables debug output until it is dropped, e.g. while a solution is benched.
+#[must_use]
+pub struct Silenced {
+    was_enabled: bool,
+}
+
+/// Disables debug output until the returned guard is dropped.
+pub fn silence() -> Silenced {
+    Silenced {
+        was_enabled: ENABLED.swap(false, Ordering::Relaxed),
+    }
+}
+
+impl Drop for Silenced {
+    fn drop(&mut self) {
+        set_enabled(self.was_enabled);
+    }
+}
+
+/// Prints to stderr like `eprintln!` if debug output is enabled, see [`debug`](crate::template::debug).
+///
+/// ```
+/// # let grid = advent_of_code::grid::Grid::filled(2, 2, '.');
+/// advent_of_code::debug!("Walked grid:\n{grid}");
+/// ```
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::template::debug::is_enabled() {
+            ::std::eprintln!($($arg)*);
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{is_enabled, set_enabled, silence};
+
+    #[test]
+    fn silences_until_dropped() {
+        set_enabled(true);
+        {
+            let _silenced = silence();
+            assert!(!is_enabled());
+        }
+        assert!(is_enabled());
+        set_enabled(false);
+    }
+}
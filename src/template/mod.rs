@@ -1,15 +1,27 @@
-use std::{env, fs};
+use std::{env, fs, path::PathBuf};
 
-pub mod aoc_cli;
+pub mod aoc_client;
+pub mod bins;
 pub mod commands;
+pub mod config;
+pub mod debug;
+pub mod output;
 pub mod runner;
+pub mod visualize;
+pub mod watchdog;
 
 pub use day::*;
+pub use puzzle::*;
+pub use year::*;
 
+mod answers;
 mod day;
+mod puzzle;
 mod readme_benchmarks;
 mod run_multi;
+mod submissions;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -17,52 +29,101 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
+    let filepath = data_dir(puzzle.year)
+        .join(folder)
+        .join(format!("{}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
+    let filepath = data_dir(puzzle.year)
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{}-{part}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Path of the data directory of a year, e.g. `data/2024`.
+#[must_use]
+pub fn data_dir(year: Year) -> PathBuf {
+    let cwd = env::current_dir().unwrap();
+    cwd.join(&config::config().data_dir).join(year.to_string())
+}
+
+/// Creates the constant `PUZZLE` and sets up the input and runner for each part.
+///
+/// The first parameter identifies the puzzle by year and day, e.g. `solution!("2024-06")`.
+///
+/// The optional `parse` parameter declares that the solution has a `parse(input: &str)` function.
+/// Its output is computed (and timed) once and passed by reference to both parts.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The optional, last parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Parts return an `Option<T>`, or a `Result<T, E>` if they can fail, where `T` and `E` implement `Display`.
+/// See [`PartOutput`](crate::template::runner::PartOutput).
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($puzzle:literal) => {
+        $crate::solution!(@impl $puzzle, [part_one, 1] [part_two, 2]);
+    };
+    ($puzzle:literal, 1) => {
+        $crate::solution!(@impl $puzzle, [part_one, 1]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($puzzle:literal, 2) => {
+        $crate::solution!(@impl $puzzle, [part_two, 2]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($puzzle:literal, parse) => {
+        $crate::solution!(@impl_parse $puzzle, parse, [part_one, 1] [part_two, 2]);
+    };
+    ($puzzle:literal, parse, 1) => {
+        $crate::solution!(@impl_parse $puzzle, parse, [part_one, 1]);
+    };
+    ($puzzle:literal, parse, 2) => {
+        $crate::solution!(@impl_parse $puzzle, parse, [part_two, 2]);
+    };
+
+    (@impl $puzzle:expr, $( [$func:expr, $part:expr] )*) => {
+        /// The current puzzle.
+        const PUZZLE: $crate::template::PuzzleId = $crate::template::PuzzleId::__parse($puzzle);
+
+        #[cfg(feature = "dhat-heap")]
+        #[global_allocator]
+        static ALLOC: dhat::Alloc = dhat::Alloc;
+
+        fn main() {
+            $crate::template::runner::run_solution(&$crate::template::runner::Solution {
+                puzzle: PUZZLE,
+                run: |input, options| {
+                    use $crate::template::runner::*;
+                    vec![$( time_part($func, input, $part, options), )*]
+                },
+            });
+        }
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
-        /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+    (@impl_parse $puzzle:expr, $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        /// The current puzzle.
+        const PUZZLE: $crate::template::PuzzleId = $crate::template::PuzzleId::__parse($puzzle);
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            $crate::template::runner::run_solution(&$crate::template::runner::Solution {
+                puzzle: PUZZLE,
+                run: |input, options| {
+                    use $crate::template::runner::*;
+                    let (parsed, parse_result) = time_parse($parse, input, options);
+                    let mut results = vec![parse_result];
+                    $( results.push(time_part($func, &parsed, $part, options)); )*
+                    results
+                },
+            });
         }
     };
 }
@@ -0,0 +1,296 @@
+/// Machine-readable reporting of solution results.
+use std::{collections::HashMap, error::Error, fmt::Display, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::runner::{BenchStats, PartResult, Step};
+use crate::template::PuzzleId;
+
+/// The format in which results are reported.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable, colored output that is printed while the solution runs.
+    #[default]
+    Text,
+    /// A single JSON array with one record per part, printed once all parts ran.
+    Json,
+    /// One JSON record per line, printed as soon as a day finished running.
+    Ndjson,
+}
+
+impl FromStr for OutputFormat {
+    type Err = OutputFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "ndjson" => Ok(Self::Ndjson),
+            _ => Err(OutputFormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`OutputFormat`].
+#[derive(Debug)]
+pub struct OutputFormatFromStrError;
+
+impl Error for OutputFormatFromStrError {}
+
+impl Display for OutputFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting an output format of `text`, `json` or `ndjson`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Collects the results of one or more days and prints them in a machine-readable format.
+/// Does nothing for [`OutputFormat::Text`], as text output is printed while running.
+pub struct Reporter {
+    format: OutputFormat,
+    records: Vec<JsonValue>,
+}
+
+impl Reporter {
+    pub fn new(format: OutputFormat) -> Self {
+        Self {
+            format,
+            records: vec![],
+        }
+    }
+
+    /// Add the results of a puzzle.
+    pub fn push(&mut self, puzzle: PuzzleId, results: &[PartResult]) {
+        match self.format {
+            OutputFormat::Text => {}
+            OutputFormat::Json => {
+                self.records
+                    .extend(results.iter().map(|result| to_record(puzzle, result)));
+            }
+            OutputFormat::Ndjson => {
+                for result in results {
+                    println!("{}", to_record(puzzle, result).stringify().unwrap());
+                }
+            }
+        }
+    }
+
+    /// Print all buffered records.
+    pub fn finish(self) {
+        if self.format == OutputFormat::Json {
+            println!("{}", JsonValue::Array(self.records).format().unwrap());
+        }
+    }
+}
+
+/// Status of a step, as reported in machine-readable output.
+fn status(result: &PartResult) -> &'static str {
+    if result.is_timed_out {
+        return "timed_out";
+    }
+    match (result.step, &result.answer, &result.error) {
+        (Step::Parse, _, Some(_)) => "failed",
+        (Step::Parse, _, None) => "parsed",
+        (Step::Part(_), _, Some(_)) => "failed",
+        (Step::Part(_), Some(_), None) => "solved",
+        (Step::Part(_), None, None) => "unsolved",
+    }
+}
+
+fn to_record(puzzle: PuzzleId, result: &PartResult) -> JsonValue {
+    let JsonValue::Object(mut map) = JsonValue::from(result.stats) else {
+        unreachable!("stats serialize to a JSON object");
+    };
+
+    map.insert(
+        "year".into(),
+        JsonValue::Number(puzzle.year.into_inner().into()),
+    );
+    map.insert(
+        "day".into(),
+        JsonValue::Number(puzzle.day.into_inner().into()),
+    );
+
+    map.insert(
+        "part".into(),
+        match result.step {
+            Step::Parse => JsonValue::String("parse".into()),
+            Step::Part(part) => JsonValue::Number(part.into()),
+        },
+    );
+
+    map.insert(
+        "answer".into(),
+        result
+            .answer
+            .clone()
+            .map_or(JsonValue::Null, JsonValue::String),
+    );
+
+    map.insert(
+        "error".into(),
+        result
+            .error
+            .clone()
+            .map_or(JsonValue::Null, JsonValue::String),
+    );
+
+    map.insert("status".into(), JsonValue::String(status(result).into()));
+
+    JsonValue::Object(map)
+}
+
+/// Read a record that a solution binary printed with `--format ndjson` back into the result of a step.
+pub fn parse_record(line: &str) -> Result<PartResult, String> {
+    let json: JsonValue = line
+        .parse()
+        .map_err(|_| "Expected record to be valid JSON.")?;
+    let map = json
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("Expected record to be a JSON object.")?;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let step = match map.get("part") {
+        Some(JsonValue::String(part)) if part == "parse" => Step::Parse,
+        Some(JsonValue::Number(part)) => Step::Part(*part as u8),
+        _ => return Err("Expected record.part to be a number or `parse`.".into()),
+    };
+
+    let string = |key: &str| map.get(key).and_then(|v| v.get::<String>()).cloned();
+
+    Ok(PartResult {
+        step,
+        answer: string("answer"),
+        error: string("error"),
+        is_timed_out: string("status").as_deref() == Some("timed_out"),
+        stats: BenchStats::try_from(&json)?,
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{collections::HashMap, time::Duration};
+
+    use tinyjson::JsonValue;
+
+    use super::{parse_record, to_record, OutputFormat};
+    use crate::puzzle;
+    use crate::template::runner::{BenchStats, PartResult, Step};
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("text".parse::<OutputFormat>().unwrap(), OutputFormat::Text);
+        assert_eq!("json".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
+        assert_eq!(
+            "ndjson".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Ndjson
+        );
+        assert_eq!("xml".parse::<OutputFormat>().is_err(), true);
+    }
+
+    #[test]
+    fn serializes_part_records() {
+        let result = PartResult {
+            step: Step::Part(2),
+            answer: Some("42".into()),
+            error: None,
+            is_timed_out: false,
+            stats: BenchStats::single(Duration::from_nanos(1500)),
+        };
+
+        let record = to_record(puzzle!(2024, 7), &result);
+        let map = record.get::<HashMap<String, JsonValue>>().unwrap();
+
+        assert_eq!(map["year"], JsonValue::Number(2024.0));
+        assert_eq!(map["day"], JsonValue::Number(7.0));
+        assert_eq!(map["part"], JsonValue::Number(2.0));
+        assert_eq!(map["answer"], JsonValue::String("42".into()));
+        assert_eq!(map["status"], JsonValue::String("solved".into()));
+        assert_eq!(map["samples"], JsonValue::Number(1.0));
+        assert_eq!(map["median_nanos"], JsonValue::Number(1500.0));
+    }
+
+    #[test]
+    fn serializes_parse_records() {
+        let result = PartResult {
+            step: Step::Parse,
+            answer: None,
+            error: None,
+            is_timed_out: false,
+            stats: BenchStats::single(Duration::from_nanos(10)),
+        };
+
+        let record = to_record(puzzle!(2024, 7), &result);
+        let map = record.get::<HashMap<String, JsonValue>>().unwrap();
+
+        assert_eq!(map["part"], JsonValue::String("parse".into()));
+        assert_eq!(map["answer"], JsonValue::Null);
+        assert_eq!(map["status"], JsonValue::String("parsed".into()));
+    }
+
+    #[test]
+    fn parses_records() {
+        let results = [
+            PartResult {
+                step: Step::Parse,
+                answer: None,
+                error: None,
+                is_timed_out: false,
+                stats: BenchStats::single(Duration::from_nanos(10)),
+            },
+            PartResult {
+                step: Step::Part(1),
+                answer: Some("1\n2".into()),
+                error: None,
+                is_timed_out: false,
+                stats: BenchStats::single(Duration::from_nanos(1500)),
+            },
+            PartResult {
+                step: Step::Part(2),
+                answer: None,
+                error: Some("timed out after 60s".into()),
+                is_timed_out: true,
+                stats: BenchStats::single(Duration::from_secs(60)),
+            },
+        ];
+
+        for result in results {
+            let line = to_record(puzzle!(2024, 7), &result).stringify().unwrap();
+            assert_eq!(parse_record(&line), Ok(result));
+        }
+
+        assert!(parse_record("Part 1: 42").is_err());
+        assert!(parse_record(r#"{"part":"3"}"#).is_err());
+    }
+
+    #[test]
+    fn serializes_failed_records() {
+        let result = PartResult {
+            step: Step::Part(1),
+            answer: None,
+            error: Some("no guard found".into()),
+            is_timed_out: false,
+            stats: BenchStats::single(Duration::from_nanos(10)),
+        };
+
+        let record = to_record(puzzle!(2024, 6), &result);
+        let map = record.get::<HashMap<String, JsonValue>>().unwrap();
+
+        assert_eq!(map["answer"], JsonValue::Null);
+        assert_eq!(map["error"], JsonValue::String("no guard found".into()));
+        assert_eq!(map["status"], JsonValue::String("failed".into()));
+
+        let result = PartResult {
+            error: Some("timed out after 60s".into()),
+            is_timed_out: true,
+            ..result
+        };
+        let record = to_record(puzzle!(2024, 6), &result);
+        let map = record.get::<HashMap<String, JsonValue>>().unwrap();
+
+        assert_eq!(map["status"], JsonValue::String("timed_out".into()));
+    }
+}
@@ -0,0 +1,193 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[cfg(feature = "today")]
+use chrono::{Datelike, FixedOffset, Utc};
+
+use crate::template::{Day, Year, MAX_DAY};
+
+#[cfg(feature = "today")]
+const SERVER_UTC_OFFSET: i32 = -5;
+
+/// Identifies a puzzle by its year and day.
+///
+/// # Display
+/// This value displays as the year and the two digit day, separated by a dash.
+/// This is also the name of the solution's binary.
+///
+/// ```
+/// # use advent_of_code::template::{Day, PuzzleId, Year};
+/// let puzzle = PuzzleId::new(Year::new(2024).unwrap(), Day::new(8).unwrap());
+/// assert_eq!(puzzle.to_string(), "2024-08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Creates a [`PuzzleId`] if the day is part of the year's calendar, see [`Year::days`].
+    pub fn try_new(year: Year, day: Day) -> Result<Self, InvalidDayError> {
+        if day.into_inner() > year.days() {
+            return Err(InvalidDayError { year, day });
+        }
+        Ok(Self::new(year, day))
+    }
+
+    /// Parses a puzzle like `2024-06` in a const context, panicking if it is invalid.
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __parse(s: &str) -> Self {
+        let bytes = s.as_bytes();
+        assert!(
+            bytes.len() == 7 && bytes[4] == b'-',
+            "invalid puzzle, expecting a puzzle like `2024-06`"
+        );
+
+        let mut year: u16 = 0;
+        let mut i = 0;
+        while i < 4 {
+            assert!(bytes[i].is_ascii_digit(), "invalid year, expecting digits");
+            year = year * 10 + (bytes[i] - b'0') as u16;
+            i += 1;
+        }
+
+        assert!(
+            bytes[5].is_ascii_digit() && bytes[6].is_ascii_digit(),
+            "invalid day, expecting two digits"
+        );
+        let day = (bytes[5] - b'0') * 10 + (bytes[6] - b'0');
+
+        assert!(
+            year >= 2015,
+            "invalid year, expecting a value of 2015 or later"
+        );
+        assert!(
+            day != 0 && day <= MAX_DAY,
+            "invalid day, expecting a value between 1 and 99"
+        );
+
+        Self::new(Year::__new_unchecked(year), Day::__new_unchecked(day))
+    }
+}
+
+#[cfg(feature = "today")]
+impl PuzzleId {
+    /// Returns the current puzzle if today is part of this year's calendar in december, `None` otherwise.
+    pub fn today() -> Option<Self> {
+        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+        let today = Utc::now().with_timezone(&offset);
+        if today.month() != 12 {
+            return None;
+        }
+        Self::try_new(
+            Year::new(u16::try_from(today.year()).ok()?)?,
+            Day::new(u8::try_from(today.day()).ok()?)?,
+        )
+        .ok()
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for PuzzleId {
+    type Err = PuzzleIdFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, day) = s.split_once('-').ok_or(PuzzleIdFromStrError::Format)?;
+        let year = year.parse().map_err(|_| PuzzleIdFromStrError::Format)?;
+        let day = day.parse().map_err(|_| PuzzleIdFromStrError::Format)?;
+        Self::try_new(year, day).map_err(PuzzleIdFromStrError::InvalidDay)
+    }
+}
+
+/// An error which can be returned when parsing a [`PuzzleId`].
+#[derive(Debug)]
+pub enum PuzzleIdFromStrError {
+    Format,
+    InvalidDay(InvalidDayError),
+}
+
+impl Error for PuzzleIdFromStrError {}
+
+impl Display for PuzzleIdFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PuzzleIdFromStrError::Format => f.write_str("expecting a puzzle like `2024-06`"),
+            PuzzleIdFromStrError::InvalidDay(e) => e.fmt(f),
+        }
+    }
+}
+
+/// An error which is returned when a day is not part of a year's calendar.
+#[derive(Debug)]
+pub struct InvalidDayError {
+    year: Year,
+    day: Day,
+}
+
+impl Error for InvalidDayError {}
+
+impl Display for InvalidDayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "day {} is not part of the {} calendar, expecting a day between 1 and {}",
+            self.day.into_inner(),
+            self.year,
+            self.year.days()
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`PuzzleId`] value in a const context.
+#[macro_export]
+macro_rules! puzzle {
+    ($year:expr, $day:expr) => {
+        $crate::template::PuzzleId::new($crate::year!($year), $crate::day!($day))
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PuzzleId;
+    use crate::puzzle;
+
+    #[test]
+    fn parses_puzzle_ids() {
+        let puzzle = "2024-06".parse::<PuzzleId>().unwrap();
+        assert_eq!(puzzle, puzzle!(2024, 6));
+        assert_eq!(puzzle.to_string(), "2024-06");
+        assert!("2024-26".parse::<PuzzleId>().is_err());
+        assert!("2025-13".parse::<PuzzleId>().is_err());
+        assert!("06".parse::<PuzzleId>().is_err());
+    }
+
+    #[test]
+    fn parses_puzzle_ids_in_const_context() {
+        const PUZZLE: PuzzleId = PuzzleId::__parse("2023-25");
+        assert_eq!(PUZZLE, puzzle!(2023, 25));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_const_puzzle_ids() {
+        PuzzleId::__parse("2024-00");
+    }
+}
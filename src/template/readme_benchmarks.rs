@@ -2,10 +2,19 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
+use crate::template::config::config;
 use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::{PuzzleId, Year};
 
-static MARKER: &str = "<!--- benchmarking table --->";
+/// Marks the position in the readme where tables for new years are inserted.
+fn marker(label: &str) -> String {
+    format!("<!--- {label} --->")
+}
+
+/// Marks the start and end of the table of a year.
+fn year_marker(label: &str, year: Year) -> String {
+    format!("<!--- {label} {year} --->")
+}
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -26,12 +35,12 @@ pub struct TablePosition {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: PuzzleId) -> String {
+    format!("./src/bin/{puzzle}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,75 +61,139 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// The duration of a part in the table, or whether it failed or was not solved.
+fn table_cell(duration: Option<String>, is_failed: bool) -> String {
+    match (duration, is_failed) {
+        (Some(duration), _) => duration,
+        (None, true) => "failed".into(),
+        (None, false) => "-".into(),
+    }
+}
+
+fn construct_table(
+    prefix: &str,
+    label: &str,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+) -> String {
+    let header = format!("{prefix} Benchmarks ({year})");
+    let marker = year_marker(label, year);
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        marker.clone(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        "| Day | Parse | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: | :---:  |".into(),
     ];
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+        let path = get_path_for_bin(PuzzleId::new(year, timing.day));
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | `{}` | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            timing.parse.unwrap_or_else(|| "-".into()),
+            table_cell(timing.part_1, timing.part_1_error.is_some()),
+            table_cell(timing.part_2, timing.part_2_error.is_some())
         ));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
+/// Replace the table of a year. If the year has no table yet, it is inserted in front of the [`marker`].
+fn update_content(
+    s: &mut String,
+    label: &str,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+) -> Result<(), Error> {
+    let table = construct_table("##", label, year, timings, total_millis);
+    let year_marker = year_marker(label, year);
+
+    if s.contains(&year_marker) {
+        let positions = locate_table(s, &year_marker)?;
+        s.replace_range(positions.pos_start..positions.pos_end, &table);
+    } else {
+        // NOTE: a table between two markers was created by an earlier version of this template, replace it.
+        let marker = marker(label);
+        let positions = locate_table(s, &marker)?;
+        s.replace_range(
+            positions.pos_start..positions.pos_end,
+            &format!("{table}\n\n{marker}"),
+        );
+    }
+
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
+pub fn update(year: Year, timings: Timings) -> Result<(), Error> {
+    let path = &config().readme;
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(
+        &mut readme,
+        &config().benchmark_marker,
+        year,
+        timings,
+        total_millis,
+    )?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{table_cell, update_content};
+    use crate::{day, template::timings::Timing, template::timings::Timings, year};
+
+    static LABEL: &str = "benchmarking table";
+    static MARKER: &str = "<!--- benchmarking table --->";
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
+                    parse: Some("5ms".into()),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 9e+10,
                 },
             ],
@@ -131,49 +204,72 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, LABEL, year!(2024), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, LABEL, year!(2024), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        update_content(&mut s, LABEL, year!(2024), get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.contains("## Benchmarks (2024)"), true);
+        assert_eq!(s.matches(MARKER).count(), 1);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
-        assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+        update_content(&mut s, LABEL, year!(2024), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, LABEL, year!(2024), get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.matches(MARKER).count(), 1);
+        assert_eq!(s.matches("<!--- benchmarking table 2024 --->").count(), 2);
+        assert_eq!(s.matches("## Benchmarks (2024)").count(), 1);
+    }
+
+    #[test]
+    fn adds_tables_for_multiple_years() {
+        let mut s = format!("foo\n{}\nbaz", MARKER);
+        update_content(&mut s, LABEL, year!(2023), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, LABEL, year!(2024), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, LABEL, year!(2023), get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.matches(MARKER).count(), 1);
+        assert_eq!(s.matches("## Benchmarks (2023)").count(), 1);
+        assert_eq!(s.matches("## Benchmarks (2024)").count(), 1);
+        assert!(s.find("(2023)").unwrap() < s.find("(2024)").unwrap());
+    }
+
+    #[test]
+    fn marks_failed_parts() {
+        assert_eq!(table_cell(Some("1ms".into()), false), "1ms");
+        assert_eq!(table_cell(None, true), "failed");
+        assert_eq!(table_cell(None, false), "-");
     }
 
     #[test]
     fn format_benchmarks() {
-        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let mut s = format!("foo\nbar\n{}\nbaz", MARKER);
+        update_content(&mut s, LABEL, year!(2024), get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
-            "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "<!--- benchmarking table 2024 --->",
+            "## Benchmarks (2024)",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2024-01.rs) | `-` | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2024-02.rs) | `5ms` | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/2024-04.rs) | `-` | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
+            "<!--- benchmarking table 2024 --->",
+            "",
             "<!--- benchmarking table --->",
             "baz",
         ]
@@ -1,257 +1,213 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    process,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::bins::{self, Bin, Bins, BuildOptions};
+use crate::template::output::{OutputFormat, Reporter};
+use crate::template::runner::{print_step, PartResult};
+use crate::template::{data_dir, Day, PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
-
-    let mut need_space = false;
-
+/// Build the solutions of a set of days of a year once, then run their binaries.
+/// Days without a solution or without an input file are reported as not solved.
+///
+/// With more than one job, days run concurrently. Their output is buffered and printed in the order of the days.
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    is_timed: bool,
+    format: OutputFormat,
+    jobs: usize,
+) -> Option<Timings> {
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let days: Vec<Day> = all_days(year)
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+        .collect();
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    let bins = build(year, &days);
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    let is_text = format == OutputFormat::Text;
+    let is_parallel = jobs > 1 && days.len() > 1;
+    let run = |day| run_day(&bins, PuzzleId::new(year, day), is_timed);
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
+    let mut report = Report {
+        year,
+        reporter: Reporter::new(format),
+        timings: Vec::with_capacity(days.len()),
+        is_text,
+        need_space: false,
+    };
+
+    if is_parallel {
+        run_parallel(&days, jobs, run, |day, outcome| {
+            report.start(day);
+            report.finish(day, outcome);
         });
+    } else {
+        for day in days {
+            report.start(day);
+            report.finish(day, run(day));
+        }
+    }
+
+    report.reporter.finish();
 
     if is_timed {
-        let timings = Timings { data: timings };
-        let total_millis = timings.total_millis();
-        println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
-        );
+        let timings = Timings {
+            data: report.timings,
+        };
+        if is_text {
+            let total_millis = timings.total_millis();
+            println!(
+                "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+        }
         Some(timings)
     } else {
         None
     }
 }
 
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    IO(io::Error),
-}
-
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+/// Build the solutions of the given days of a year, with the profile and features of the running binary.
+/// Exits the process if cargo cannot be run.
+pub fn build(year: Year, days: &[Day]) -> Bins {
+    let puzzles: Vec<PuzzleId> = days.iter().map(|day| PuzzleId::new(year, *day)).collect();
+    match bins::build(&puzzles, &BuildOptions::current()) {
+        Ok(bins) => bins,
+        Err(e) => {
+            eprintln!("Failed to build solutions: {e}");
+            process::exit(1);
+        }
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// What happened when a day was run.
+pub enum DayOutcome {
+    NotSolved,
+    NoInput,
+    /// The solution failed to compile, with the compiler errors.
+    CompileError(String),
+    /// The solution binary did not report results, e.g. because it crashed.
+    Failed(String),
+    Ran(Vec<PartResult>),
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
-    use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
+/// Run the binary of a day, if it compiled and its input exists.
+pub fn run_day(bins: &Bins, puzzle: PuzzleId, is_timed: bool) -> DayOutcome {
+    let executable = match bins.get(puzzle) {
+        Bin::Missing => return DayOutcome::NotSolved,
+        Bin::Failed(errors) => return DayOutcome::CompileError(errors.to_string()),
+        Bin::Compiled(executable) => executable,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
+    if !has_input(puzzle) {
+        return DayOutcome::NoInput;
+    }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    match bins::run(executable, is_timed) {
+        Ok(results) => DayOutcome::Ran(results),
+        Err(error) => DayOutcome::Failed(error),
+    }
+}
 
-        if is_release {
-            args.push("--release");
+/// Run days on `jobs` threads, passing their outcomes to `report` in the order of `days`.
+fn run_parallel(
+    days: &[Day],
+    jobs: usize,
+    run: impl Fn(Day) -> DayOutcome + Sync,
+    mut report: impl FnMut(Day, DayOutcome),
+) {
+    let next_day = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let (next_day, run, sender) = (&next_day, &run, sender.clone());
+            scope.spawn(move || {
+                while let Some(day) = days.get(next_day.fetch_add(1, Ordering::Relaxed)) {
+                    if sender.send((*day, run(*day))).is_err() {
+                        break;
+                    }
+                }
+            });
         }
-
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        drop(sender);
+
+        // NOTE: days finish in any order, hold back their outcomes until all earlier days were reported.
+        let mut finished = BTreeMap::new();
+        let mut pending = days.iter().peekable();
+        for (day, outcome) in receiver {
+            finished.insert(day, outcome);
+            while let Some(outcome) = pending.peek().and_then(|day| finished.remove(*day)) {
+                report(*pending.next().unwrap(), outcome);
+            }
         }
+    });
+}
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
-
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
-
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
-
-        let mut output = vec![];
-
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+/// Prints the report of each day and collects its records and timings.
+struct Report {
+    year: Year,
+    reporter: Reporter,
+    timings: Vec<Timing>,
+    is_text: bool,
+    need_space: bool,
+}
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+impl Report {
+    fn start(&mut self, day: Day) {
+        if !self.is_text {
+            return;
         }
+        if self.need_space {
+            println!();
+        }
+        self.need_space = true;
 
-        thread.join().unwrap();
-        cmd.wait()?;
-
-        Ok(output)
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
-
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+    fn finish(&mut self, day: Day, outcome: DayOutcome) {
+        match outcome {
+            DayOutcome::NotSolved if self.is_text => println!("Not solved."),
+            DayOutcome::NoInput if self.is_text => println!("No input file."),
+            DayOutcome::NotSolved | DayOutcome::NoInput => {}
+            DayOutcome::CompileError(errors) if self.is_text => {
+                println!("✖ Failed to compile.");
+                println!("{}", errors.trim_end());
+            }
+            DayOutcome::Failed(error) if self.is_text => println!("✖ {error}"),
+            // NOTE: keep stdout parseable for machine-readable formats.
+            DayOutcome::CompileError(errors) => {
+                eprintln!("Day {day} failed to compile:\n{}", errors.trim_end());
+            }
+            DayOutcome::Failed(error) => eprintln!("Day {day} failed: {error}"),
+            DayOutcome::Ran(results) => {
+                if self.is_text {
+                    results.iter().for_each(print_step);
                 }
-
-                timings.total_nanos += nanos;
-            });
-
-        timings
-    }
-
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
-    }
-
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
-
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
-
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+                self.reporter.push(PuzzleId::new(self.year, day), &results);
+                self.timings.push(Timing::from_results(day, &results));
+            }
         }
     }
 }
+
+/// Checks whether the input of a puzzle has been downloaded.
+pub fn has_input(puzzle: PuzzleId) -> bool {
+    data_dir(puzzle.year)
+        .join("inputs")
+        .join(format!("{}.txt", puzzle.day))
+        .exists()
+}
@@ -2,34 +2,336 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
-use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::answers::Answers;
+use crate::template::aoc_client::{AocClient, AocClientError, Outcome};
+use crate::template::config::config;
+use crate::template::debug;
+use crate::template::output::{OutputFormat, Reporter};
+use crate::template::submissions::Submissions;
+use crate::template::visualize::{self, RenderFormat};
+use crate::template::watchdog;
+use crate::template::{read_file, PuzzleId, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Entry point of a solution binary: runs the solution against the day's input,
+/// reports the results in the requested format and submits an answer if requested.
+pub fn run_solution(solution: &Solution) {
+    let format = parse_format_arg();
+    let options = RunOptions {
+        is_timed: env::args().any(|x| x == "--time"),
+        is_quiet: format != OutputFormat::Text,
+    };
+
+    debug::set_enabled(env::args().any(|x| x == "--verbose" || x == "-v"));
+    let is_visualized = env::args().any(|x| x == "--visualize");
+    let render = parse_render_arg();
+    visualize::set_recording(is_visualized || render.is_some());
+
+    let input = read_file("inputs", solution.puzzle);
+    let results = watchdog::run(solution, input, options, config().part_timeout);
+
+    let mut reporter = Reporter::new(format);
+    reporter.push(solution.puzzle, &results);
+    reporter.finish();
+
+    if is_visualized || render.is_some() {
+        show_frames(solution.puzzle, is_visualized, render);
+    }
+
+    for result in results {
+        if let (Step::Part(part), Some(answer)) = (result.step, result.answer) {
+            submit_result(answer, solution.puzzle, part);
+        }
+    }
+}
+
+/// Replay the frames that the solution recorded with [`visualize::record`] and render them to files.
+fn show_frames(puzzle: PuzzleId, is_visualized: bool, render: Option<RenderFormat>) {
+    visualize::set_recording(false);
+    let frames = visualize::take_frames();
+
+    if frames.is_empty() {
+        eprintln!("The solution did not record any frames.");
+        return;
+    }
+
+    #[cfg(feature = "visualize")]
+    {
+        if let Some(format) = render {
+            let dir = crate::template::data_dir(puzzle.year)
+                .join("visualizations")
+                .join(puzzle.day.to_string());
+            match visualize::export(&frames, format, &dir, &config().palette) {
+                Ok(path) => println!("Rendered {} frames to {}", frames.len(), path.display()),
+                Err(e) => eprintln!("Failed to render visualization: {e}"),
+            }
+        }
+
+        if is_visualized {
+            if let Err(e) = visualize::play(&frames) {
+                eprintln!("Failed to play visualization: {e}");
+            }
+        }
+    }
+
+    #[cfg(not(feature = "visualize"))]
+    {
+        let _ = (puzzle, is_visualized, render);
+        eprintln!(
+            "Recorded {} frames, but the `visualize` feature is disabled. Format: cargo solve 1 --visualize",
+            frames.len()
+        );
+    }
+}
+
+/// Parse the `--render` argument passed to a solution binary.
+fn parse_render_arg() -> Option<RenderFormat> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--render")?;
+
+    match args.get(index + 1).map(|x| x.parse()) {
+        Some(Ok(format)) => Some(format),
+        Some(Err(e)) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+        None => {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --render gif");
+            process::exit(1);
+        }
+    }
+}
+
+/// Parse the `--format` argument passed to a solution binary, defaulting to text output.
+fn parse_format_arg() -> OutputFormat {
+    let args: Vec<String> = env::args().collect();
+
+    let Some(index) = args.iter().position(|x| x == "--format") else {
+        return OutputFormat::default();
+    };
+
+    match args.get(index + 1).map(|x| x.parse()) {
+        Some(Ok(format)) => format,
+        Some(Err(e)) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+        None => {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --format json");
+            process::exit(1);
+        }
+    }
+}
+
+/// The return value of a solution part.
+///
+/// Parts return an `Option<T>` if they may not find an answer, or a `Result<T, E>` if they fail with an error.
+pub trait PartOutput {
+    /// The answer as text, `Ok(None)` if there is none, or the message of the error the part failed with.
+    fn answer(&self) -> Result<Option<String>, String>;
+}
+
+impl<T: Display> PartOutput for Option<T> {
+    fn answer(&self) -> Result<Option<String>, String> {
+        Ok(self.as_ref().map(ToString::to_string))
+    }
+}
+
+impl<T: Display, E: Display> PartOutput for Result<T, E> {
+    fn answer(&self) -> Result<Option<String>, String> {
+        match self {
+            Ok(answer) => Ok(Some(answer.to_string())),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+/// Run and print a solution part, returning its answer and timing as a structured result.
+/// A panic of the part is caught and reported as an error.
+pub fn time_part<I: Copy, R: PartOutput>(
+    func: impl Fn(I) -> R,
+    input: I,
+    part: u8,
+    options: RunOptions,
+) -> PartResult {
+    let step = Step::Part(part);
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let timer = Instant::now();
+    let output = watchdog::catch_panic(|| {
+        run_timed(func, input, step, options, |output| {
+            print_result(&output.answer(), &part_str, "");
+        })
+    });
+    let (answer, stats) = match output {
+        Ok((output, stats)) => (output.answer(), stats),
+        Err(error) => (Err(error), BenchStats::single(timer.elapsed())),
+    };
+
+    let (answer_str, error) = match &answer {
+        Ok(answer) => (answer.clone(), None),
+        Err(error) => (None, Some(error.clone())),
+    };
+
+    let result = PartResult {
+        step,
+        answer: answer_str,
+        error,
+        is_timed_out: false,
+        stats,
+    };
+    watchdog::finish(&result);
+
+    if !options.is_quiet {
+        print_result(&answer, &part_str, &format_duration(&stats));
+    }
+
+    result
+}
+
+/// Run and print the `parse` step of a solution, returning its output alongside its timing.
+pub fn time_parse<'a, T>(
+    func: impl Fn(&'a str) -> T,
+    input: &'a str,
+    options: RunOptions,
+) -> (T, PartResult) {
+    let (parsed, stats) = run_timed(func, input, Step::Parse, options, |_| print!("Parse:"));
+
+    let result = PartResult {
+        step: Step::Parse,
+        answer: None,
+        error: None,
+        is_timed_out: false,
+        stats,
+    };
+    watchdog::finish(&result);
+
+    if !options.is_quiet {
+        print!("\r");
+        println!("Parse:{}", format_duration(&stats));
+    }
+
+    (parsed, result)
+}
+
+/// Options that control how the steps of a solution are run.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunOptions {
+    /// Bench every step instead of running it once.
+    pub is_timed: bool,
+    /// Do not print human-readable progress and results, e.g. when reporting JSON.
+    pub is_quiet: bool,
+}
+
+/// The steps of a solution binary, see [`solution!`](crate::solution).
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub puzzle: PuzzleId,
+    /// Runs all steps of the solution against an input.
+    pub run: fn(&str, RunOptions) -> Vec<PartResult>,
+}
+
+/// A step of a solution that is timed on its own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Step {
+    Parse,
+    Part(u8),
+}
+
+/// The outcome of running a single step of a solution.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartResult {
+    pub step: Step,
+    /// The answer of a part, `None` for the parse step and for parts that did not return a result.
+    pub answer: Option<String>,
+    /// The message of the error that a step failed with, e.g. the message and location of a panic.
+    pub error: Option<String>,
+    /// Whether the step was stopped waiting for after the `part_timeout` of `aoc.toml`.
+    pub is_timed_out: bool,
+    pub stats: BenchStats,
+}
+
+/// Summary statistics over the samples collected when running a solution part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchStats {
+    pub samples: u128,
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+    pub p95: Duration,
+    pub std_dev: Duration,
+    /// Number of samples outside of the Tukey fences (1.5 × IQR beyond the quartiles).
+    pub outliers: u128,
+}
+
+impl BenchStats {
+    /// Stats for a part that was executed exactly once.
+    pub fn single(duration: Duration) -> Self {
+        Self {
+            samples: 1,
+            mean: duration,
+            median: duration,
+            min: duration,
+            p95: duration,
+            std_dev: Duration::ZERO,
+            outliers: 0,
+        }
+    }
+
+    /// Computes stats from a set of samples. Returns `None` if `samples` is empty.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let len = sorted.len();
+        let nanos: Vec<f64> = sorted.iter().map(|x| x.as_nanos() as f64).collect();
+        let mean = nanos.iter().sum::<f64>() / len as f64;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / len as f64;
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+        let q1 = percentile(&sorted, 25);
+        let q3 = percentile(&sorted, 75);
+        let iqr = q3.saturating_sub(q1);
+        let fence_low = q1.saturating_sub(iqr * 3 / 2);
+        let fence_high = q3 + iqr * 3 / 2;
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+        Some(Self {
+            samples: len as u128,
+            mean: Duration::from_secs_f64(mean / 1e9),
+            median: percentile(&sorted, 50),
+            min: sorted[0],
+            p95: percentile(&sorted, 95),
+            std_dev: Duration::from_secs_f64(variance.sqrt() / 1e9),
+            outliers: sorted
+                .iter()
+                .filter(|x| **x < fence_low || **x > fence_high)
+                .count() as u128,
+        })
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+/// Nearest-rank percentile of an already sorted, non-empty slice.
+fn percentile(sorted: &[Duration], pct: usize) -> Duration {
+    let rank = (pct * sorted.len()).div_ceil(100);
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Run a solution part. The behavior differs depending on whether the part is timed:
+///  1. by default, the function is executed once.
+///  2. when timed, the function is benched (approx. the benchmark budget set in `aoc.toml` or 10 samples, whatever take longer.)
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
+    step: Step,
+    options: RunOptions,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, BenchStats) {
+    watchdog::start(step);
     let timer = Instant::now();
     let result = {
         #[cfg(feature = "dhat-heap")]
@@ -38,26 +340,44 @@ fn run_timed<I: Copy, T>(
         func(input)
     };
     let base_time = timer.elapsed();
+    watchdog::ran();
 
-    hook(&result);
+    if !options.is_quiet {
+        hook(&result);
+    }
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let stats = if options.is_timed {
+        bench(func, input, &base_time, options.is_quiet)
     } else {
-        (base_time, 1)
+        BenchStats::single(base_time)
     };
 
-    (result, run.0, run.1)
+    (result, stats)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Bench a solution part. A warm-up phase of a tenth of the iterations is run first and discarded,
+/// so that cold caches and lazy initialization do not skew the collected samples.
+/// Debug output and frames are only produced by the first run, not while benching.
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    is_quiet: bool,
+) -> BenchStats {
+    if !is_quiet {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    let _silenced = debug::silence();
+    let _paused = visualize::pause();
 
     let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+        (config().bench_budget.as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+
+    for _ in 0..cmp::max(bench_iterations / 10, 1) {
+        black_box(func(black_box(input)));
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -67,35 +387,48 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    // NOTE: `bench_iterations` is at least 10, so there are always samples.
+    BenchStats::from_samples(&timers).unwrap()
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn format_duration(stats: &BenchStats) -> String {
+    if stats.samples == 1 {
+        format!(" ({:.1?})", stats.median)
+    } else {
+        format!(
+            " ({:.1?} @ {} samples) [min {:.1?}, p95 {:.1?}, mean {:.1?}, sd {:.1?}, {} outliers]",
+            stats.median,
+            stats.samples,
+            stats.min,
+            stats.p95,
+            stats.mean,
+            stats.std_dev,
+            stats.outliers
+        )
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+/// Print the result of a step after it ran, e.g. in a solution binary or because it failed outside of [`time_part`].
+pub(crate) fn print_step(result: &PartResult) {
+    let duration = format_duration(&result.stats);
+    let answer = match &result.error {
+        Some(error) => Err(error.clone()),
+        None => Ok(result.answer.clone()),
+    };
+
+    match result.step {
+        Step::Parse if answer.is_ok() => println!("Parse:{duration}"),
+        Step::Parse => print_result(&answer, "Parse", &duration),
+        Step::Part(part) => print_result(&answer, &format!("Part {part}"), &duration),
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(answer: &Result<Option<String>, String>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
+    match answer {
+        Ok(Some(result)) => {
+            if result.contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
@@ -114,7 +447,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Ok(None) => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -122,17 +455,26 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Err(error) => {
+            if is_intermediate_result {
+                print!("{part}: ✖ {error}");
+            } else {
+                print!("\r");
+                println!("{part}: ✖ {error}{duration_str}");
+            }
+        }
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. the answer is not known to be wrong from earlier submissions.
+///  3. a session cookie is configured.
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    puzzle: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<Outcome, AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -155,11 +497,93 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    let answer = result.to_string();
+
+    // NOTE: without the log, known wrong answers cannot be rejected and it would be overwritten after submitting.
+    let mut submissions = match Submissions::read_from_file(puzzle.year) {
+        Ok(submissions) => submissions,
+        Err(e) => {
+            eprintln!("Not submitting {answer}: failed to read the submission log: {e}");
+            process::exit(1);
+        }
+    };
+    if let Err(rejection) = submissions.check(puzzle.day, part, &answer) {
+        eprintln!("Not submitting {answer}: {rejection}");
+        return None;
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let client = match AocClient::from_config() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to submit result: {e}");
+            process::exit(1);
+        }
+    };
+
+    println!("Submitting result...");
+    let output = client.submit(puzzle, part, &answer);
+
+    match &output {
+        Ok(outcome) => {
+            println!("{outcome}");
+
+            submissions.push(puzzle.day, part, &answer, *outcome);
+            if let Err(e) = submissions.store_file(puzzle.year) {
+                eprintln!("Failed to store submission: {e}");
+            }
+
+            if *outcome == Outcome::Correct {
+                match Answers::read_from_file(puzzle.year) {
+                    Ok(mut answers) => {
+                        answers.set(puzzle.day, part, &answer);
+                        match answers.store_file(puzzle.year) {
+                            Ok(()) => println!("Stored accepted answer for `cargo verify`."),
+                            Err(e) => eprintln!("Failed to store accepted answer: {e}"),
+                        }
+                    }
+                    Err(e) => eprintln!(
+                        "Failed to store accepted answer, could not read stored answers: {e}"
+                    ),
+                }
+            }
+        }
+        Err(e) => eprintln!("{e}"),
+    }
+
+    Some(output)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::BenchStats;
+    use std::time::Duration;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn computes_stats() {
+        let stats = BenchStats::from_samples(&nanos(&[40, 10, 30, 20, 50])).unwrap();
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.median, Duration::from_nanos(30));
+        assert_eq!(stats.mean, Duration::from_nanos(30));
+        assert_eq!(stats.p95, Duration::from_nanos(50));
+        assert_eq!(stats.std_dev.as_nanos(), 14);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn counts_outliers() {
+        let stats =
+            BenchStats::from_samples(&nanos(&[10, 10, 11, 11, 12, 12, 13, 13, 1000])).unwrap();
+        assert_eq!(stats.median, Duration::from_nanos(12));
+        assert_eq!(stats.outliers, 1);
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(BenchStats::from_samples(&[]), None);
+    }
 }
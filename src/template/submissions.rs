@@ -0,0 +1,302 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::Outcome;
+use crate::template::{data_dir, Day, Year};
+
+static SUBMISSIONS_FILE: &str = "submissions.json";
+
+/// Represents a single answer that was submitted, alongside the server's verdict.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+}
+
+/// Represents the log of all submissions.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+/// The reason why an answer is not worth submitting.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rejection {
+    /// The same answer was submitted before and was wrong.
+    KnownWrong,
+    /// An answer lower than or equal to this one was too high.
+    AboveBound(String),
+    /// An answer higher than or equal to this one was too low.
+    BelowBound(String),
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::KnownWrong => write!(f, "this answer was already submitted and is wrong."),
+            Rejection::AboveBound(bound) => {
+                write!(f, "the answer {bound} was already too high.")
+            }
+            Rejection::BelowBound(bound) => {
+                write!(f, "the answer {bound} was already too low.")
+            }
+        }
+    }
+}
+
+impl Submissions {
+    /// Dehydrate the log to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(data_dir(year).join(SUBMISSIONS_FILE))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the log from a JSON file. If not present, returns an empty log.
+    /// Fails if the file cannot be read or is malformed, so that it is not overwritten by accident.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        match fs::read_to_string(data_dir(year).join(SUBMISSIONS_FILE)) {
+            Ok(contents) => Submissions::try_from(contents),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Record a submission.
+    pub fn push(&mut self, day: Day, part: u8, answer: &str, outcome: Outcome) {
+        self.data.push(Submission {
+            day,
+            part,
+            answer: answer.into(),
+            outcome,
+        });
+    }
+
+    /// Check an answer against earlier submissions of a part.
+    /// Returns a [`Rejection`] if the answer is known to be wrong without asking the server.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), Rejection> {
+        let submissions = self.data.iter().filter(|s| s.day == day && s.part == part);
+
+        let value = answer.parse::<i128>().ok();
+
+        for submission in submissions {
+            if submission.answer == answer && submission.outcome.is_wrong() {
+                return Err(Rejection::KnownWrong);
+            }
+
+            let (Some(value), Ok(bound)) = (value, submission.answer.parse::<i128>()) else {
+                continue;
+            };
+
+            match submission.outcome {
+                Outcome::TooHigh if value >= bound => {
+                    return Err(Rejection::AboveBound(submission.answer.clone()))
+                }
+                Outcome::TooLow if value <= bound => {
+                    return Err(Rejection::BelowBound(submission.answer.clone()))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn outcome_to_str(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::Correct => "correct",
+        Outcome::Incorrect => "incorrect",
+        Outcome::TooHigh => "too_high",
+        Outcome::TooLow => "too_low",
+        Outcome::AlreadySolved => "already_solved",
+        Outcome::RateLimited(_) => "rate_limited",
+    }
+}
+
+fn outcome_from_str(s: &str) -> Option<Outcome> {
+    match s {
+        "correct" => Some(Outcome::Correct),
+        "incorrect" => Some(Outcome::Incorrect),
+        "too_high" => Some(Outcome::TooHigh),
+        "too_low" => Some(Outcome::TooLow),
+        "already_solved" => Some(Outcome::AlreadySolved),
+        "rate_limited" => Some(Outcome::RateLimited(None)),
+        _ => None,
+    }
+}
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "outcome".into(),
+            JsonValue::String(outcome_to_str(value.outcome).into()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let outcome = json
+            .get("outcome")
+            .and_then(|v| v.get::<String>())
+            .and_then(|v| outcome_from_str(v))
+            .ok_or("Expected submission.outcome to be a known outcome.")?;
+
+        Ok(Submission {
+            day,
+            part: *part as u8,
+            answer: answer.clone(),
+            outcome,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::day;
+    use crate::template::aoc_client::Outcome;
+
+    use super::{Rejection, Submissions};
+
+    fn get_mock_submissions() -> Submissions {
+        let mut submissions = Submissions::default();
+        submissions.push(day!(6), 1, "4000", Outcome::TooHigh);
+        submissions.push(day!(6), 1, "100", Outcome::TooLow);
+        submissions.push(day!(6), 1, "2000", Outcome::Incorrect);
+        submissions.push(day!(6), 2, "abc", Outcome::Incorrect);
+        submissions
+    }
+
+    #[test]
+    fn rejects_known_wrong_answers() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check(day!(6), 1, "2000"),
+            Err(Rejection::KnownWrong)
+        );
+        assert_eq!(
+            submissions.check(day!(6), 2, "abc"),
+            Err(Rejection::KnownWrong)
+        );
+    }
+
+    #[test]
+    fn rejects_answers_outside_bounds() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check(day!(6), 1, "4001"),
+            Err(Rejection::AboveBound("4000".into()))
+        );
+        assert_eq!(
+            submissions.check(day!(6), 1, "99"),
+            Err(Rejection::BelowBound("100".into()))
+        );
+    }
+
+    #[test]
+    fn accepts_plausible_answers() {
+        let submissions = get_mock_submissions();
+        assert_eq!(submissions.check(day!(6), 1, "1999"), Ok(()));
+        assert_eq!(submissions.check(day!(6), 2, "4001"), Ok(()));
+        assert_eq!(submissions.check(day!(7), 1, "2000"), Ok(()));
+    }
+
+    #[test]
+    fn serializes_submissions() {
+        let submissions = get_mock_submissions();
+        let json = tinyjson::JsonValue::from(submissions.clone())
+            .stringify()
+            .unwrap();
+        let parsed = Submissions::try_from(json).unwrap();
+        assert_eq!(parsed.data, submissions.data);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_unknown_outcomes() {
+        let json = r#"{ "data": [{ "day": "06", "part": 1, "answer": "1", "outcome": "maybe" }] }"#
+            .to_string();
+        Submissions::try_from(json).unwrap();
+    }
+}
@@ -1,19 +1,116 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    io::Error,
+    str::FromStr,
+    time::Duration,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::runner::{BenchStats, PartResult, Step};
+use crate::template::{data_dir, Day, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE: &str = "timings.json";
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub parse_stats: Option<BenchStats>,
+    pub part_1_stats: Option<BenchStats>,
+    pub part_2_stats: Option<BenchStats>,
+    /// The error message of a part that failed.
+    pub part_1_error: Option<String>,
+    pub part_2_error: Option<String>,
     pub total_nanos: f64,
 }
 
+impl Timing {
+    /// Collect the timings of a day from the results of its steps.
+    /// Parts that did not produce an answer are not timed, the errors of failed parts are recorded instead.
+    pub fn from_results(day: Day, results: &[PartResult]) -> Self {
+        let mut timing = Timing {
+            day,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            part_1_error: None,
+            part_2_error: None,
+            total_nanos: 0_f64,
+        };
+
+        for result in results {
+            match (result.step, &result.error) {
+                (Step::Part(1), Some(error)) => timing.part_1_error = Some(error.clone()),
+                (Step::Part(2), Some(error)) => timing.part_2_error = Some(error.clone()),
+                _ => {}
+            }
+
+            if result.step != Step::Parse && result.answer.is_none() {
+                continue;
+            }
+
+            let duration = Some(format!("{:.1?}", result.stats.median));
+            let stats = Some(result.stats);
+
+            match result.step {
+                Step::Parse => (timing.parse, timing.parse_stats) = (duration, stats),
+                Step::Part(1) => (timing.part_1, timing.part_1_stats) = (duration, stats),
+                Step::Part(2) => (timing.part_2, timing.part_2_stats) = (duration, stats),
+                Step::Part(_) => continue,
+            }
+
+            timing.total_nanos += result.stats.median.as_nanos() as f64;
+        }
+
+        timing
+    }
+
+    /// The stats of a step, if it was timed.
+    pub fn stats(&self, step: Step) -> Option<&BenchStats> {
+        match step {
+            Step::Parse => self.parse_stats.as_ref(),
+            Step::Part(1) => self.part_1_stats.as_ref(),
+            Step::Part(2) => self.part_2_stats.as_ref(),
+            Step::Part(_) => None,
+        }
+    }
+}
+
+/// The change of the median time of a step between stored and fresh timings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Delta {
+    pub day: Day,
+    pub step: Step,
+    pub stored: Duration,
+    /// `None` if the step is no longer timed, e.g. because it failed or its day did not run.
+    pub fresh: Option<Duration>,
+}
+
+impl Delta {
+    /// The relative change in percent, positive if the step got slower.
+    pub fn percent(&self) -> Option<f64> {
+        let stored = self.stored.as_nanos() as f64;
+        let fresh = self.fresh?.as_nanos() as f64;
+        if stored == 0_f64 {
+            return Some(0_f64);
+        }
+        Some((fresh - stored) / stored * 100_f64)
+    }
+
+    /// Whether the step got slower by more than `threshold` percent, or lost its timing.
+    pub fn is_regression(&self, threshold: u64) -> bool {
+        self.percent()
+            .is_none_or(|percent| percent > threshold as f64)
+    }
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -23,15 +120,15 @@ pub struct Timings {
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(data_dir(year).join(TIMINGS_FILE))?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(data_dir(year).join(TIMINGS_FILE))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
@@ -51,10 +148,34 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
+    /// Compare `fresh` timings of the given days to `self`, step by step.
+    /// Only steps with stored stats are compared, e.g. not steps stored before their stats were recorded.
+    /// A stored step that is missing from `fresh` is compared as well, see [`Delta::fresh`].
+    pub fn compare(&self, fresh: &Self, days: &HashSet<Day>) -> Vec<Delta> {
+        let mut deltas = vec![];
+
+        for stored in self.data.iter().filter(|t| days.contains(&t.day)) {
+            let timing = fresh.data.iter().find(|t| t.day == stored.day);
+            for step in [Step::Parse, Step::Part(1), Step::Part(2)] {
+                if let Some(before) = stored.stats(step) {
+                    deltas.push(Delta {
+                        day: stored.day,
+                        step,
+                        stored: before.median,
+                        fresh: timing.and_then(|t| t.stats(step)).map(|after| after.median),
+                    });
+                }
+            }
+        }
+
+        deltas.sort_by_key(|delta| delta.day);
+        deltas
+    }
+
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
@@ -114,6 +235,14 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
+        map.insert(
+            "parse".into(),
+            value
+                .parse
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
 
@@ -133,6 +262,37 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "part_1_error".into(),
+            value
+                .part_1_error
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        map.insert(
+            "part_2_error".into(),
+            value
+                .part_2_error
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        map.insert(
+            "parse_stats".into(),
+            value.parse_stats.map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_1_stats".into(),
+            value.part_1_stats.map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_2_stats".into(),
+            value.part_2_stats.map_or(JsonValue::Null, JsonValue::from),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -166,10 +326,47 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: parse timings, stats and errors are optional to stay compatible with timings stored by older versions.
+        let parse = json
+            .get("parse")
+            .and_then(|v| if v.is_null() { None } else { v.get::<String>() });
+
+        let part_1_error =
+            json.get("part_1_error")
+                .and_then(|v| if v.is_null() { None } else { v.get::<String>() });
+
+        let part_2_error =
+            json.get("part_2_error")
+                .and_then(|v| if v.is_null() { None } else { v.get::<String>() });
+
+        let parse_stats = match json.get("parse_stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(BenchStats::try_from(v)?),
+        };
+
+        let part_1_stats = match json.get("part_1_stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(BenchStats::try_from(v)?),
+        };
+
+        let part_2_stats = match json.get("part_2_stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(BenchStats::try_from(v)?),
+        };
+
         Ok(Timing {
             day,
+            parse: parse.cloned(),
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            parse_stats,
+            part_1_stats,
+            part_2_stats,
+            part_1_error: part_1_error.cloned(),
+            part_2_error: part_2_error.cloned(),
             total_nanos,
         })
     }
@@ -177,6 +374,59 @@ impl TryFrom<&JsonValue> for Timing {
 
 /* -------------------------------------------------------------------------- */
 
+impl From<BenchStats> for JsonValue {
+    fn from(value: BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        for (key, duration) in [
+            ("mean_nanos", value.mean),
+            ("median_nanos", value.median),
+            ("min_nanos", value.min),
+            ("p95_nanos", value.p95),
+            ("std_dev_nanos", value.std_dev),
+        ] {
+            map.insert(key.into(), JsonValue::Number(duration.as_nanos() as f64));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected timing stats.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let duration = |key: &str| number(key).map(|x| Duration::from_nanos(x as u64));
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(BenchStats {
+            samples: number("samples")? as u128,
+            mean: duration("mean_nanos")?,
+            median: duration("median_nanos")?,
+            min: duration("min_nanos")?,
+            p95: duration("p95_nanos")?,
+            std_dev: duration("std_dev_nanos")?,
+            outliers: number("outliers")? as u128,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 #[cfg(feature = "test_lib")]
 mod tests {
     use crate::day;
@@ -188,20 +438,38 @@ mod tests {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 4e+10,
                 },
             ],
@@ -223,6 +491,15 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_failed_parts() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": null, "part_2": null, "part_1_error": "no guard found", "total_nanos": 0 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_error, Some("no guard found".to_string()));
+            assert_eq!(timing.part_2_error, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -268,6 +545,74 @@ mod tests {
         }
     }
 
+    mod from_results {
+        use std::time::Duration;
+
+        use crate::{
+            day,
+            template::runner::{BenchStats, PartResult, Step},
+            template::timings::Timing,
+        };
+
+        fn result(step: Step, answer: Option<&str>, nanos: u64) -> PartResult {
+            PartResult {
+                step,
+                answer: answer.map(Into::into),
+                error: None,
+                is_timed_out: false,
+                stats: BenchStats::single(Duration::from_nanos(nanos)),
+            }
+        }
+
+        #[test]
+        fn collects_steps() {
+            let timing = Timing::from_results(
+                day!(1),
+                &[
+                    result(Step::Parse, None, 1_500),
+                    result(Step::Part(1), Some("42"), 2_000_000),
+                    result(Step::Part(2), Some("43"), 30),
+                ],
+            );
+            assert_eq!(timing.parse, Some("1.5µs".into()));
+            assert_eq!(timing.part_1, Some("2.0ms".into()));
+            assert_eq!(timing.part_2, Some("30.0ns".into()));
+            assert_eq!(timing.part_2_stats.unwrap().samples, 1);
+            assert_eq!(timing.total_nanos, 2_001_530_f64);
+        }
+
+        #[test]
+        fn skips_unsolved_parts() {
+            let timing = Timing::from_results(
+                day!(1),
+                &[
+                    result(Step::Part(1), Some("42"), 1_000),
+                    result(Step::Part(2), None, 1_000),
+                ],
+            );
+            assert_eq!(timing.parse, None);
+            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.part_2_stats, None);
+            assert_eq!(timing.part_2_error, None);
+            assert_eq!(timing.total_nanos, 1_000_f64);
+        }
+
+        #[test]
+        fn records_failed_parts() {
+            let failed = PartResult {
+                error: Some("no guard found".into()),
+                is_timed_out: false,
+                ..result(Step::Part(1), None, 1_000)
+            };
+            let timing =
+                Timing::from_results(day!(1), &[failed, result(Step::Part(2), Some("43"), 1_000)]);
+            assert_eq!(timing.part_1, None);
+            assert_eq!(timing.part_1_error, Some("no guard found".into()));
+            assert_eq!(timing.part_2, Some("1.0µs".into()));
+            assert_eq!(timing.total_nanos, 1_000_f64);
+        }
+    }
+
     mod is_day_complete {
         use crate::{
             day,
@@ -279,13 +624,19 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +644,19 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -307,13 +664,19 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -331,8 +694,14 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -351,8 +720,14 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_error: None,
+                    part_2_error: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -381,4 +756,107 @@ mod tests {
             assert_eq!(merged.data.len(), 3);
         }
     }
+
+    mod compare {
+        use std::{collections::HashSet, time::Duration};
+
+        use crate::{
+            day,
+            template::runner::{BenchStats, PartResult, Step},
+            template::timings::{Delta, Timing, Timings},
+            template::Day,
+        };
+
+        use super::get_mock_timings;
+
+        fn result(part: u8, nanos: u64) -> PartResult {
+            PartResult {
+                step: Step::Part(part),
+                answer: Some("42".into()),
+                error: None,
+                is_timed_out: false,
+                stats: BenchStats::single(Duration::from_nanos(nanos)),
+            }
+        }
+
+        fn timing(day: Day, part_1: u64, part_2: u64) -> Timing {
+            Timing::from_results(day, &[result(1, part_1), result(2, part_2)])
+        }
+
+        fn days(days: &[Day]) -> HashSet<Day> {
+            days.iter().copied().collect()
+        }
+
+        #[test]
+        fn compares_timed_steps() {
+            let stored = Timings {
+                data: vec![timing(day!(1), 1_000, 2_000), timing(day!(2), 1_000, 1_000)],
+            };
+            let fresh = Timings {
+                data: vec![timing(day!(1), 1_200, 1_000), timing(day!(3), 1_000, 1_000)],
+            };
+
+            let deltas = stored.compare(&fresh, &days(&[day!(1), day!(3)]));
+            assert_eq!(
+                deltas,
+                vec![
+                    Delta {
+                        day: day!(1),
+                        step: Step::Part(1),
+                        stored: Duration::from_nanos(1_000),
+                        fresh: Some(Duration::from_nanos(1_200)),
+                    },
+                    Delta {
+                        day: day!(1),
+                        step: Step::Part(2),
+                        stored: Duration::from_nanos(2_000),
+                        fresh: Some(Duration::from_nanos(1_000)),
+                    },
+                ]
+            );
+            assert_eq!(deltas[0].percent().unwrap().round(), 20_f64);
+            assert_eq!(deltas[1].percent().unwrap().round(), -50_f64);
+        }
+
+        #[test]
+        fn skips_steps_without_stats() {
+            let fresh = Timings {
+                data: vec![timing(day!(1), 1_000, 1_000)],
+            };
+            assert!(get_mock_timings()
+                .compare(&fresh, &days(&[day!(1)]))
+                .is_empty());
+        }
+
+        #[test]
+        fn flags_lost_timings() {
+            let stored = Timings {
+                data: vec![timing(day!(1), 1_000, 1_000), timing(day!(2), 1_000, 1_000)],
+            };
+            let fresh = Timings {
+                data: vec![Timing::from_results(day!(1), &[result(1, 1_000)])],
+            };
+            assert_eq!(fresh.data[0].part_2, None);
+
+            let deltas = stored.compare(&fresh, &days(&[day!(1), day!(2)]));
+            assert_eq!(deltas.len(), 4);
+            assert!(!deltas[0].is_regression(10));
+            assert_eq!(deltas[1].step, Step::Part(2));
+            assert_eq!(deltas[1].fresh, None);
+            assert!(deltas[1].is_regression(10));
+            assert!(deltas[2..].iter().all(|delta| delta.is_regression(10)));
+        }
+
+        #[test]
+        fn flags_regressions_beyond_threshold() {
+            let delta = Delta {
+                day: day!(1),
+                step: Step::Parse,
+                stored: Duration::from_nanos(1_000),
+                fresh: Some(Duration::from_nanos(1_120)),
+            };
+            assert!(delta.is_regression(10));
+            assert!(!delta.is_regression(15));
+        }
+    }
 }
@@ -0,0 +1,282 @@
+/// Renders recorded frames to files, either as an animated GIF or as one SVG per frame.
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt::Write as _,
+    fs::{self, File},
+    io::{self, BufWriter},
+    path::{Path, PathBuf},
+};
+
+use super::{Frame, RenderFormat};
+use crate::point::Point2;
+
+type Rgb = [u8; 3];
+
+/// The color of `.`, which is also used to fill the area around frames of different sizes.
+const BACKGROUND: Rgb = [15, 15, 35];
+
+/// Hundredths of a second that each frame is shown in a GIF. The last frame is held longer before it loops.
+const FRAME_DELAY: u16 = 10;
+const LAST_FRAME_DELAY: u16 = 200;
+
+/// Renders the frames into `dir`, replacing earlier renders of the same format. Returns the path that was written.
+///
+/// Cells are colored by their character, looked up in `palette` first, highlights take precedence.
+pub fn export(
+    frames: &[Frame],
+    format: RenderFormat,
+    dir: &Path,
+    palette: &HashMap<char, Rgb>,
+) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    match format {
+        RenderFormat::Gif => {
+            let path = dir.join("animation.gif");
+            write_gif(frames, palette, &path)?;
+            Ok(path)
+        }
+        RenderFormat::Svg => {
+            remove_svgs(dir)?;
+            for (i, frame) in frames.iter().enumerate() {
+                fs::write(
+                    dir.join(format!("frame-{:04}.svg", i + 1)),
+                    svg(frame, palette),
+                )?;
+            }
+            Ok(dir.to_path_buf())
+        }
+    }
+}
+
+/// Colors that are used for the characters of the puzzles, unless the palette says otherwise.
+fn default_color(c: char) -> Rgb {
+    match c {
+        '.' | ' ' => BACKGROUND,
+        '#' => [204, 204, 204],
+        // Heights and other digits, from dark to bright green.
+        '0'..='9' => {
+            let level = c as u8 - b'0';
+            [0, 60 + level * 19, 0]
+        }
+        _ => [150, 150, 150],
+    }
+}
+
+fn cell_color(frame: &Frame, point: Point2, palette: &HashMap<char, Rgb>) -> Rgb {
+    if let Some(color) = frame.highlights.get(&point) {
+        return color.rgb();
+    }
+    let c = frame.grid[point];
+    palette.get(&c).copied().unwrap_or_else(|| default_color(c))
+}
+
+/// Removes the frames of an earlier render, which may have had more frames.
+fn remove_svgs(dir: &Path) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_frame = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("frame-") && name.ends_with(".svg"));
+        if is_frame {
+            fs::remove_file(path)?;
+        }
+    }
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Number of pixels per cell, so that small grids are not tiny and large grids are not huge.
+fn cell_size(width: usize, height: usize) -> usize {
+    (640 / width.max(height).max(1)).clamp(1, 16)
+}
+
+/// Colors of all pixels of a frame on a canvas of the given size, row by row.
+fn pixels(
+    frame: &Frame,
+    palette: &HashMap<char, Rgb>,
+    (width, height): (usize, usize),
+    cell_size: usize,
+) -> Vec<Rgb> {
+    let mut pixels = vec![palette.get(&'.').copied().unwrap_or(BACKGROUND); width * height];
+    for point in frame.grid.points() {
+        let color = cell_color(frame, point, palette);
+        let (x, y) = (point.x as usize * cell_size, point.y as usize * cell_size);
+        for row in pixels[y * width..].chunks_mut(width).take(cell_size) {
+            row[x..x + cell_size].fill(color);
+        }
+    }
+    pixels
+}
+
+fn write_gif(frames: &[Frame], palette: &HashMap<char, Rgb>, path: &Path) -> io::Result<()> {
+    let columns = frames.iter().map(|f| f.grid.width()).max().unwrap_or(0);
+    let rows = frames.iter().map(|f| f.grid.height()).max().unwrap_or(0);
+    let cell_size = cell_size(columns, rows);
+    let size = (columns * cell_size, rows * cell_size);
+    let (Ok(width), Ok(height)) = (u16::try_from(size.0), u16::try_from(size.1)) else {
+        return Err(io::Error::other("frames are too large for a GIF"));
+    };
+
+    // GIFs store indices into a color table, which is shared by all frames.
+    let mut colors: Vec<Rgb> = vec![];
+    let mut indices: HashMap<Rgb, u8> = HashMap::new();
+    let mut indexed_frames = Vec::with_capacity(frames.len());
+    for frame in frames {
+        let mut buffer = Vec::with_capacity(size.0 * size.1);
+        for color in pixels(frame, palette, size, cell_size) {
+            let index = match indices.get(&color) {
+                Some(index) => *index,
+                None => {
+                    let index = u8::try_from(colors.len())
+                        .map_err(|_| io::Error::other("frames use more than 256 colors"))?;
+                    colors.push(color);
+                    indices.insert(color, index);
+                    index
+                }
+            };
+            buffer.push(index);
+        }
+        indexed_frames.push(buffer);
+    }
+
+    let mut encoder = gif::Encoder::new(
+        BufWriter::new(File::create(path)?),
+        width,
+        height,
+        &colors.concat(),
+    )
+    .map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+
+    let count = indexed_frames.len();
+    for (i, buffer) in indexed_frames.into_iter().enumerate() {
+        let frame = gif::Frame {
+            width,
+            height,
+            delay: if i + 1 == count {
+                LAST_FRAME_DELAY
+            } else {
+                FRAME_DELAY
+            },
+            buffer: Cow::Owned(buffer),
+            ..gif::Frame::default()
+        };
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn hex(color: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", color[0], color[1], color[2])
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Draws a frame as an SVG with one unit per cell and the caption below the grid.
+fn svg(frame: &Frame, palette: &HashMap<char, Rgb>) -> String {
+    let (width, height) = (frame.grid.width(), frame.grid.height());
+    let cell_size = cell_size(width, height);
+    let background = palette.get(&'.').copied().unwrap_or(BACKGROUND);
+    let caption_height = if frame.caption.is_empty() { 0 } else { 2 };
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {width} {}\">\n",
+        width * cell_size,
+        (height + caption_height) * cell_size,
+        height + caption_height,
+    );
+    let _ = writeln!(
+        svg,
+        "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>",
+        hex(background)
+    );
+
+    for point in frame.grid.points() {
+        let color = cell_color(frame, point, palette);
+        if color != background {
+            let _ = writeln!(
+                svg,
+                "<rect x=\"{}\" y=\"{}\" width=\"1\" height=\"1\" fill=\"{}\"/>",
+                point.x,
+                point.y,
+                hex(color)
+            );
+        }
+    }
+
+    if !frame.caption.is_empty() {
+        let _ = writeln!(
+            svg,
+            "<text x=\"0.5\" y=\"{}\" font-family=\"monospace\" font-size=\"1\" fill=\"#cccccc\">{}</text>",
+            height + 1,
+            escape(&frame.caption)
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{cell_size, pixels, svg, BACKGROUND};
+    use crate::{
+        grid::Grid,
+        point::Point2,
+        template::visualize::{Color, Frame},
+    };
+
+    fn frame() -> Frame {
+        Frame::from_grid(&Grid::parse(".#\n#.", |c| c), |c| *c)
+            .highlight(Point2::new(1, 1), Color::Red)
+    }
+
+    #[test]
+    fn colors_pixels() {
+        let palette = HashMap::from([('#', [1, 2, 3])]);
+        let pixels = pixels(&frame(), &palette, (5, 4), 2);
+        assert_eq!(pixels.len(), 20);
+        assert_eq!(
+            &pixels[0..5],
+            &[BACKGROUND, BACKGROUND, [1, 2, 3], [1, 2, 3], BACKGROUND]
+        );
+        assert_eq!(
+            &pixels[10..15],
+            &[
+                [1, 2, 3],
+                [1, 2, 3],
+                Color::Red.rgb(),
+                Color::Red.rgb(),
+                BACKGROUND
+            ]
+        );
+        assert_eq!(cell_size(10, 10), 16);
+        assert_eq!(cell_size(140, 130), 4);
+    }
+
+    #[test]
+    fn draws_svgs() {
+        let svg = svg(&frame().caption("a < b"), &HashMap::new());
+        assert!(svg.contains("width=\"32\" height=\"64\" viewBox=\"0 0 2 4\""));
+        assert!(svg.contains("<rect x=\"1\" y=\"0\" width=\"1\" height=\"1\" fill=\"#cccccc\"/>"));
+        assert!(svg.contains("<rect x=\"1\" y=\"1\" width=\"1\" height=\"1\" fill=\"#ff5555\"/>"));
+        assert!(!svg.contains("x=\"0\" y=\"0\""));
+        assert!(svg.contains(">a &lt; b</text>"));
+    }
+}
@@ -0,0 +1,257 @@
+/// Frames that a solution records to replay how it arrives at its answer, shown with `cargo solve NN --visualize`
+/// or rendered to files with `cargo solve NN --render gif`.
+///
+/// Recording is only switched on for the first run of each part of a visualized solution. [`record`] takes a closure,
+/// so frames are not even built in normal and benchmark runs.
+///
+/// ```
+/// # use advent_of_code::{grid::Grid, point::Point2};
+/// use advent_of_code::template::visualize::{self, Color, Frame};
+///
+/// let grid = Grid::parse("..#\n...", |c| c == '#');
+/// let guard = Point2::new(0, 1);
+/// visualize::record(|| {
+///     Frame::from_grid(&grid, |wall| if *wall { '#' } else { '.' })
+///         .highlight(guard, Color::Red)
+///         .caption("The guard starts walking")
+/// });
+/// ```
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::Display,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+use crate::{grid::Grid, point::Point2};
+
+#[cfg(feature = "visualize")]
+mod export;
+#[cfg(feature = "visualize")]
+mod terminal;
+
+#[cfg(feature = "visualize")]
+pub use export::export;
+#[cfg(feature = "visualize")]
+pub use terminal::play;
+
+static RECORDING: AtomicBool = AtomicBool::new(false);
+static FRAMES: Mutex<Vec<Frame>> = Mutex::new(vec![]);
+
+/// A color to highlight cells of a frame with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    /// The ANSI escape code that sets this color as the foreground color.
+    pub fn ansi(&self) -> &'static str {
+        match self {
+            Color::Red => "\x1b[1;31m",
+            Color::Green => "\x1b[1;32m",
+            Color::Yellow => "\x1b[1;33m",
+            Color::Blue => "\x1b[1;34m",
+            Color::Magenta => "\x1b[1;35m",
+            Color::Cyan => "\x1b[1;36m",
+        }
+    }
+
+    /// The color in rendered visualizations.
+    pub fn rgb(&self) -> [u8; 3] {
+        match self {
+            Color::Red => [255, 85, 85],
+            Color::Green => [0, 204, 0],
+            Color::Yellow => [255, 255, 102],
+            Color::Blue => [85, 136, 255],
+            Color::Magenta => [255, 85, 255],
+            Color::Cyan => [85, 255, 255],
+        }
+    }
+}
+
+/// The file format that frames are rendered to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenderFormat {
+    /// A single animated GIF.
+    Gif,
+    /// One SVG file per frame.
+    Svg,
+}
+
+impl FromStr for RenderFormat {
+    type Err = RenderFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "gif" => Ok(Self::Gif),
+            "svg" => Ok(Self::Svg),
+            _ => Err(RenderFormatFromStrError),
+        }
+    }
+}
+
+impl Display for RenderFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenderFormat::Gif => f.write_str("gif"),
+            RenderFormat::Svg => f.write_str("svg"),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`RenderFormat`].
+#[derive(Debug)]
+pub struct RenderFormatFromStrError;
+
+impl Error for RenderFormatFromStrError {}
+
+impl Display for RenderFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a render format of `gif` or `svg`")
+    }
+}
+
+/// A snapshot of a grid with highlighted cells.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    pub grid: Grid<char>,
+    pub highlights: HashMap<Point2, Color>,
+    /// Describes what happens in this frame, shown below the grid.
+    pub caption: String,
+}
+
+impl Frame {
+    /// Creates a frame from a grid, drawing each cell as the character returned by `f`.
+    pub fn from_grid<T>(grid: &Grid<T>, f: impl FnMut(&T) -> char) -> Self {
+        Self {
+            grid: grid.map(f),
+            highlights: HashMap::new(),
+            caption: String::new(),
+        }
+    }
+
+    /// Highlights a cell. A later highlight of the same cell replaces an earlier one.
+    pub fn highlight(mut self, point: Point2, color: Color) -> Self {
+        self.highlights.insert(point, color);
+        self
+    }
+
+    /// Highlights several cells with the same color.
+    pub fn highlight_all(mut self, points: impl IntoIterator<Item = Point2>, color: Color) -> Self {
+        self.highlights
+            .extend(points.into_iter().map(|point| (point, color)));
+        self
+    }
+
+    pub fn caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = caption.into();
+        self
+    }
+
+    /// Draws the grid with ANSI colors, one line per row.
+    pub fn to_ansi(&self) -> String {
+        let mut lines = Vec::with_capacity(self.grid.height());
+        let mut line = String::new();
+        for (point, c) in self.grid.iter() {
+            match self.highlights.get(&point) {
+                Some(color) => {
+                    line.push_str(color.ansi());
+                    line.push(*c);
+                    line.push_str("\x1b[0m");
+                }
+                None => line.push(*c),
+            }
+            if point.x as usize == self.grid.width() - 1 {
+                lines.push(std::mem::take(&mut line));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+/// Checks whether frames are recorded.
+pub fn is_recording() -> bool {
+    RECORDING.load(Ordering::Relaxed)
+}
+
+/// Starts or stops recording frames.
+pub fn set_recording(recording: bool) {
+    RECORDING.store(recording, Ordering::Relaxed);
+}
+
+/// Records the frame built by `frame`, if frames are recorded. Otherwise `frame` is not called.
+pub fn record(frame: impl FnOnce() -> Frame) {
+    if is_recording() {
+        FRAMES.lock().unwrap().push(frame());
+    }
+}
+
+/// Removes and returns all frames that were recorded so far.
+pub fn take_frames() -> Vec<Frame> {
+    std::mem::take(&mut *FRAMES.lock().unwrap())
+}
+
+/// Stops recording frames until it is dropped, e.g. while a solution is benched.
+#[must_use]
+pub struct Paused {
+    was_recording: bool,
+}
+
+/// Stops recording frames until the returned guard is dropped.
+pub fn pause() -> Paused {
+    Paused {
+        was_recording: RECORDING.swap(false, Ordering::Relaxed),
+    }
+}
+
+impl Drop for Paused {
+    fn drop(&mut self) {
+        set_recording(self.was_recording);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{pause, record, set_recording, take_frames, Color, Frame};
+    use crate::{grid::Grid, point::Point2};
+
+    #[test]
+    fn draws_highlights() {
+        let grid = Grid::parse("ab\ncd", |c| c);
+        let frame = Frame::from_grid(&grid, |c| c.to_ascii_uppercase())
+            .highlight_all([Point2::new(1, 0), Point2::new(0, 1)], Color::Red)
+            .highlight(Point2::new(0, 1), Color::Blue);
+        assert_eq!(frame.to_ansi(), "A\x1b[1;31mB\x1b[0m\n\x1b[1;34mC\x1b[0mD");
+    }
+
+    #[test]
+    fn records_frames_only_when_enabled() {
+        let grid = Grid::filled(1, 1, '.');
+        record(|| unreachable!("frames are not built when not recording"));
+
+        set_recording(true);
+        record(|| Frame::from_grid(&grid, |c| *c).caption("first"));
+        {
+            let _paused = pause();
+            record(|| unreachable!("frames are not built while paused"));
+        }
+        record(|| Frame::from_grid(&grid, |c| *c).caption("second"));
+        set_recording(false);
+
+        let captions: Vec<_> = take_frames().into_iter().map(|f| f.caption).collect();
+        assert_eq!(captions, vec!["first", "second"]);
+        assert!(take_frames().is_empty());
+    }
+}
@@ -0,0 +1,136 @@
+/// Replays recorded frames in the terminal.
+use std::{
+    io::{self, stdout, Write},
+    time::Duration,
+};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::Print,
+    terminal::{self, ClearType},
+};
+
+use super::Frame;
+use crate::template::{ANSI_ITALIC, ANSI_RESET};
+
+const INITIAL_DELAY: Duration = Duration::from_millis(100);
+const MIN_DELAY: Duration = Duration::from_millis(1);
+const MAX_DELAY: Duration = Duration::from_secs(2);
+
+/// Restores the terminal when playback ends, also if it ends with an error.
+struct Screen;
+
+impl Screen {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// What a key press asks the player to do.
+enum Action {
+    TogglePause,
+    Next,
+    Previous,
+    Faster,
+    Slower,
+    Quit,
+}
+
+fn action(key: KeyEvent) -> Option<Action> {
+    if key.kind != KeyEventKind::Press {
+        return None;
+    }
+    match key.code {
+        KeyCode::Char(' ') => Some(Action::TogglePause),
+        KeyCode::Right | KeyCode::Char('l') => Some(Action::Next),
+        KeyCode::Left | KeyCode::Char('h') => Some(Action::Previous),
+        KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Up => Some(Action::Faster),
+        KeyCode::Char('-') | KeyCode::Down => Some(Action::Slower),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        KeyCode::Char('q') | KeyCode::Esc => Some(Action::Quit),
+        _ => None,
+    }
+}
+
+/// Plays the frames until the user quits. Playback stops at the last frame.
+///
+/// Space pauses, the arrow keys step through the frames and `+` / `-` change the speed.
+pub fn play(frames: &[Frame]) -> io::Result<()> {
+    if frames.is_empty() {
+        return Ok(());
+    }
+
+    let _screen = Screen::enter()?;
+    let mut index = 0;
+    let mut delay = INITIAL_DELAY;
+    let mut is_paused = false;
+
+    loop {
+        let is_last = index == frames.len() - 1;
+        draw(&frames[index], index, frames.len(), delay, is_paused)?;
+
+        // Wait for a key while paused and at the end, otherwise only until the next frame is due.
+        let is_due = !is_paused && !is_last && !event::poll(delay)?;
+        if is_due {
+            index += 1;
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+
+        match action(key) {
+            Some(Action::TogglePause) => is_paused = !is_paused,
+            Some(Action::Next) => {
+                is_paused = true;
+                index = (index + 1).min(frames.len() - 1);
+            }
+            Some(Action::Previous) => {
+                is_paused = true;
+                index = index.saturating_sub(1);
+            }
+            Some(Action::Faster) => delay = (delay / 2).max(MIN_DELAY),
+            Some(Action::Slower) => delay = (delay * 2).min(MAX_DELAY),
+            Some(Action::Quit) => return Ok(()),
+            None => {}
+        }
+    }
+}
+
+fn draw(
+    frame: &Frame,
+    index: usize,
+    count: usize,
+    delay: Duration,
+    is_paused: bool,
+) -> io::Result<()> {
+    let mut out = stdout();
+    let state = if is_paused { "paused" } else { "playing" };
+
+    queue!(
+        out,
+        cursor::MoveTo(0, 0),
+        terminal::Clear(ClearType::All),
+        // Raw mode does not move the cursor back to the start of a line on `\n`.
+        Print(frame.to_ansi().replace('\n', "\r\n")),
+        Print("\r\n\r\n"),
+        Print(&frame.caption),
+        Print("\r\n"),
+        Print(format!(
+            "{ANSI_ITALIC}Frame {}/{count} ({state}, {delay:?} per frame) · space: pause · ←/→: step · +/-: speed · q: quit{ANSI_RESET}",
+            index + 1
+        )),
+    )?;
+    out.flush()
+}
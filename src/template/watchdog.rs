@@ -0,0 +1,347 @@
+/// Runs solutions on a worker thread, so that a part that panics or never finishes does not take down the runner.
+///
+/// Parts catch their own panics and are reported as failed. The runner tells the watchdog when the first run of a
+/// step starts and ends. If it takes longer than the `part_timeout` of `aoc.toml`, the step is reported as timed out
+/// and the watchdog returns. Threads cannot be stopped from the outside, so the abandoned worker keeps running in the
+/// background until the step finishes, but it does not print or report anything afterwards.
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, RecvTimeoutError, Sender},
+        Arc, Once,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::template::runner::{print_step, BenchStats, PartResult, RunOptions, Solution, Step};
+
+/// Progress of a worker, as reported to its watchdog.
+enum Event {
+    /// The first run of a step started.
+    Started(Step, Instant),
+    /// The first run of the running step ended. It is benched next, which is not subject to the timeout.
+    Ran,
+    Finished(PartResult),
+    /// A panic that was not caught by a part, e.g. in the `parse` function.
+    Panicked(String),
+}
+
+/// The connection of a worker thread to its watchdog.
+struct Worker {
+    events: Sender<Event>,
+    is_abandoned: Arc<AtomicBool>,
+}
+
+/// The payload that ends an abandoned worker.
+struct Abandoned;
+
+thread_local! {
+    static WORKER: RefCell<Option<Worker>> = const { RefCell::new(None) };
+    /// Whether panics on this thread are caught by [`catch_panic`], instead of printed by the default hook.
+    static IS_CATCHING: Cell<bool> = const { Cell::new(false) };
+    static PANIC_MESSAGE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Runs all steps of a solution on a worker thread and waits until they finished, failed or timed out.
+///
+/// If a step times out or the `parse` step panics, the result of that step is the last one returned.
+pub fn run(
+    solution: &Solution,
+    input: String,
+    options: RunOptions,
+    timeout: Option<Duration>,
+) -> Vec<PartResult> {
+    let (events, receiver) = mpsc::channel();
+    let is_abandoned = Arc::new(AtomicBool::new(false));
+    let worker = Worker {
+        events: events.clone(),
+        is_abandoned: is_abandoned.clone(),
+    };
+    let run = solution.run;
+
+    thread::spawn(move || {
+        WORKER.set(Some(worker));
+        if let Err(error) = catch_panic(|| run(&input, options)) {
+            let _ = events.send(Event::Panicked(error));
+        }
+    });
+
+    let mut results = vec![];
+    let mut running: Option<(Step, Instant)> = None;
+
+    loop {
+        let event = match (running, timeout) {
+            (Some((_, started)), Some(timeout)) => {
+                receiver.recv_timeout((started + timeout).saturating_duration_since(Instant::now()))
+            }
+            _ => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match event {
+            Ok(Event::Started(step, started)) => running = Some((step, started)),
+            Ok(Event::Ran) => running = None,
+            Ok(Event::Finished(result)) => {
+                running = None;
+                results.push(result);
+            }
+            Ok(Event::Panicked(error)) => {
+                // NOTE: a panic outside of a step, e.g. between two parts, fails the step that would run next.
+                let (step, duration) = match running.take() {
+                    Some((step, started)) => (step, started.elapsed()),
+                    None => (next_step(&results), Duration::ZERO),
+                };
+                results.push(failure(step, error, duration, false, options));
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                is_abandoned.store(true, Ordering::Relaxed);
+                if let (Some((step, started)), Some(timeout)) = (running, timeout) {
+                    let error = format!("timed out after {timeout:?}");
+                    results.push(failure(step, error, started.elapsed(), true, options));
+                }
+                break;
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    results
+}
+
+/// The step after the last finished one.
+fn next_step(results: &[PartResult]) -> Step {
+    match results.last().map(|result| result.step) {
+        None => Step::Parse,
+        Some(Step::Parse) => Step::Part(1),
+        Some(Step::Part(part)) => Step::Part(part + 1),
+    }
+}
+
+fn failure(
+    step: Step,
+    error: String,
+    duration: Duration,
+    is_timed_out: bool,
+    options: RunOptions,
+) -> PartResult {
+    let result = PartResult {
+        step,
+        answer: None,
+        error: Some(error),
+        is_timed_out,
+        stats: BenchStats::single(duration),
+    };
+    if !options.is_quiet {
+        print_step(&result);
+    }
+    result
+}
+
+/// Runs `f`, returning the message and location of a panic instead of printing it.
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !IS_CATCHING.get() {
+                default_hook(info);
+                return;
+            }
+            let message = payload_message(info.payload());
+            let message = match info.location() {
+                Some(location) => format!("panicked at {location}: {message}"),
+                None => format!("panicked: {message}"),
+            };
+            PANIC_MESSAGE.set(Some(message));
+        }));
+    });
+
+    let was_catching = IS_CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    IS_CATCHING.set(was_catching);
+
+    result.map_err(|payload| {
+        PANIC_MESSAGE
+            .take()
+            .unwrap_or_else(|| format!("panicked: {}", payload_message(&*payload)))
+    })
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "Box<dyn Any>"
+    }
+}
+
+fn send(event: Event) {
+    WORKER.with_borrow(|worker| {
+        if let Some(worker) = worker {
+            let _ = worker.events.send(event);
+        }
+    });
+}
+
+/// Ends the worker if its watchdog gave up on it.
+fn end_if_abandoned() {
+    let is_abandoned = WORKER.with_borrow(|worker| {
+        worker
+            .as_ref()
+            .is_some_and(|worker| worker.is_abandoned.load(Ordering::Relaxed))
+    });
+    if is_abandoned {
+        panic::resume_unwind(Box::new(Abandoned));
+    }
+}
+
+/// Tells the watchdog that the first run of a step starts.
+pub(crate) fn start(step: Step) {
+    send(Event::Started(step, Instant::now()));
+}
+
+/// Tells the watchdog that the first run of the running step ended.
+pub(crate) fn ran() {
+    end_if_abandoned();
+    send(Event::Ran);
+}
+
+/// Reports the result of a step to the watchdog.
+pub(crate) fn finish(result: &PartResult) {
+    end_if_abandoned();
+    send(Event::Finished(result.clone()));
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{catch_panic, run};
+    use crate::puzzle;
+    use crate::template::runner::{time_parse, time_part, RunOptions, Solution, Step};
+
+    const QUIET: RunOptions = RunOptions {
+        is_timed: false,
+        is_quiet: true,
+    };
+
+    fn part_one(input: &str) -> Option<usize> {
+        Some(input.len())
+    }
+
+    fn part_two(input: &str) -> Option<usize> {
+        if input.is_empty() {
+            panic!("empty input");
+        }
+        loop {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    fn parse(input: &str) -> usize {
+        input.parse().unwrap()
+    }
+
+    #[test]
+    fn catches_panics() {
+        assert_eq!(catch_panic(|| 1), Ok(1));
+        let error = catch_panic(|| panic!("at the disco")).unwrap_err();
+        assert!(error.starts_with("panicked at src/template/watchdog.rs:"));
+        assert!(error.ends_with(": at the disco"));
+    }
+
+    #[test]
+    fn reports_panicking_parts() {
+        let solution = Solution {
+            puzzle: puzzle!(2024, 1),
+            run: |input, options| {
+                vec![
+                    time_part(part_two, input, 2, options),
+                    time_part(part_one, input, 1, options),
+                ]
+            },
+        };
+
+        let results = run(&solution, String::new(), QUIET, None);
+        assert_eq!(results.len(), 2);
+        assert!(results[0]
+            .error
+            .as_ref()
+            .unwrap()
+            .ends_with(": empty input"));
+        assert!(!results[0].is_timed_out);
+        assert_eq!(results[1].answer, Some("0".into()));
+    }
+
+    #[test]
+    fn reports_panicking_parse_steps() {
+        let solution = Solution {
+            puzzle: puzzle!(2024, 1),
+            run: |input, options| {
+                let (parsed, parse_result) = time_parse(parse, input, options);
+                vec![
+                    parse_result,
+                    time_part(|x: &usize| Some(*x), &parsed, 1, options),
+                ]
+            },
+        };
+
+        let results = run(&solution, "x".into(), QUIET, None);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].step, Step::Parse);
+        assert!(results[0].error.is_some());
+
+        let results = run(&solution, "4".into(), QUIET, None);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1].answer, Some("4".into()));
+    }
+
+    #[test]
+    fn reports_panics_between_steps() {
+        let solution = Solution {
+            puzzle: puzzle!(2024, 1),
+            run: |input, options| {
+                let result = time_part(part_one, input, 1, options);
+                panic!("lost {result:?}");
+            },
+        };
+
+        let results = run(&solution, "abc".into(), QUIET, None);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].answer, Some("3".into()));
+        assert_eq!(results[1].step, Step::Part(2));
+        assert!(results[1].error.as_ref().unwrap().contains(": lost "));
+    }
+
+    #[test]
+    fn times_out_parts() {
+        let solution = Solution {
+            puzzle: puzzle!(2024, 1),
+            run: |input, options| {
+                vec![
+                    time_part(part_one, input, 1, options),
+                    time_part(part_two, input, 2, options),
+                ]
+            },
+        };
+
+        let results = run(
+            &solution,
+            "abc".into(),
+            QUIET,
+            Some(Duration::from_millis(50)),
+        );
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].answer, Some("3".into()));
+        assert_eq!(results[1].step, Step::Part(2));
+        assert_eq!(results[1].error, Some("timed out after 50ms".into()));
+        assert!(results[1].is_timed_out);
+    }
+}